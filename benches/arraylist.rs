@@ -0,0 +1,79 @@
+// Plain timing benchmarks, run with `cargo bench`.
+//
+// What each pair measures:
+// - `push` vs `push_reserved`: growth is extent by extent, so pushing
+//   without a reservation reallocates once every EXTENT_LEN elements, while
+//   the reserved list never reallocates. How much that costs depends on `n`
+//   and on how often the allocator can grow the block in place, so the gap
+//   between the two varies from run to run and machine to machine.
+// - `remove_thrash` vs `remove_thrash_no_shrink`: pushing and popping across
+//   an extent boundary reallocates on every step while auto-shrink is on, and
+//   never once it is off.
+// - `from_slice` vs `from_copy_slice`: the former clones element by element,
+//   the latter is a single memcpy.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use data_structures_rust::collection::arraylist::ArrayList;
+
+const ITERATIONS: u32 = 20;
+
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+	f();
+	let mut total = Duration::new(0, 0);
+	for _ in 0..ITERATIONS {
+		let start = Instant::now();
+		f();
+		total += start.elapsed();
+	}
+	println!("{:<32} {:>12?}/iter", name, total / ITERATIONS);
+}
+
+fn push(n: usize) {
+	let mut a = ArrayList::new();
+	for i in 0..n {
+		a.push(black_box(i));
+	}
+	black_box(&a);
+}
+
+fn push_reserved(n: usize) {
+	let mut a = ArrayList::with_capacity(n);
+	for i in 0..n {
+		a.push(black_box(i));
+	}
+	black_box(&a);
+}
+
+fn from_slice(data: &[u8]) {
+	black_box(ArrayList::from(black_box(data)));
+}
+
+fn from_copy_slice(data: &[u8]) {
+	black_box(ArrayList::from_copy_slice(black_box(data)));
+}
+
+fn remove_thrash(n: usize, auto_shrink: bool) {
+	let mut a: ArrayList<usize> = (0..16).collect();
+	a.set_auto_shrink(auto_shrink);
+	for i in 0..n {
+		a.push(black_box(i));
+		black_box(a.pop());
+	}
+	black_box(&a);
+}
+
+fn main() {
+	for &n in &[1_000, 100_000] {
+		bench(&format!("push/{}", n), || push(n));
+		bench(&format!("push_reserved/{}", n), || push_reserved(n));
+	}
+	let data = vec![7u8; 1_000_000];
+	bench("from_slice/1000000", || from_slice(&data));
+	bench("from_copy_slice/1000000", || from_copy_slice(&data));
+	bench("remove_thrash/100000", || remove_thrash(100_000, true));
+	bench("remove_thrash_no_shrink/100000", || {
+		remove_thrash(100_000, false)
+	});
+}
@@ -0,0 +1,82 @@
+use crate::collection::arraylist::ArrayList;
+
+/// Handle to a value allocated in an `Arena`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Id(usize);
+
+/// A grow-only arena handing out stable `Id`s.
+///
+/// Slots are never freed or reused, so an `Id` stays valid for the life of
+/// the arena even when the backing list reallocates.
+pub struct Arena<T> {
+	items: ArrayList<T>,
+}
+
+impl<T> Arena<T> {
+	pub fn new() -> Arena<T> {
+		Self::with_capacity(0)
+	}
+
+	pub fn with_capacity(cap: usize) -> Arena<T> {
+		let mut items = ArrayList::with_capacity(cap);
+		items.set_auto_shrink(false);
+		Arena { items }
+	}
+
+	pub fn alloc(&mut self, value: T) -> Id {
+		self.items.push(value);
+		Id(self.items.len() - 1)
+	}
+
+	pub fn get(&self, id: Id) -> Option<&T> {
+		self.items.at(id.0).ok()
+	}
+
+	pub fn get_mut(&mut self, id: Id) -> Option<&mut T> {
+		if id.0 < self.items.len() {
+			Some(&mut self.items[id.0])
+		} else {
+			None
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.items.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+}
+
+impl<T> Default for Arena<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn string_arena_ids_stable() {
+		let mut arena = Arena::new();
+		let a = arena.alloc("a".to_string());
+		let b = arena.alloc("b".to_string());
+		assert_eq!(arena.get(a).map(String::as_str), Some("a"));
+		assert_eq!(arena.get(b).map(String::as_str), Some("b"));
+		arena.get_mut(a).unwrap().push('!');
+
+		let extents = arena.items.extents();
+		let ids: Vec<Id> = (0..40).map(|i| arena.alloc(i.to_string())).collect();
+		assert!(arena.items.extents() > extents);
+
+		assert_eq!(arena.get(a).map(String::as_str), Some("a!"));
+		assert_eq!(arena.get(b).map(String::as_str), Some("b"));
+		for (i, &id) in ids.iter().enumerate() {
+			assert_eq!(arena.get(id), Some(&i.to_string()));
+		}
+		assert_eq!(arena.len(), 42);
+	}
+}
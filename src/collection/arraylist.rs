@@ -1,59 +1,291 @@
+use crate::collection::frozen::FrozenList;
 use std::alloc::{self, Layout};
-use std::fmt::{self, Debug};
-use std::mem::MaybeUninit;
-use std::ops::{Index, IndexMut};
+use std::borrow::{Borrow, BorrowMut};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::io;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop, MaybeUninit};
+use std::ops::{Bound, Index, IndexMut, Range, RangeBounds};
 use std::ptr;
+use std::rc::Rc;
+use std::slice;
+use std::sync::Arc;
 
 const EXTENT_LEN: usize = 16;
 
+/// How far past a `size_hint` lower bound `Extend` will reserve on the
+/// strength of the upper bound.
+const HINT_SLACK: usize = 4 * EXTENT_LEN;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const BASE64_DIGITS: &[u8; 64] =
+	b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Marker for types for which the all-zero byte pattern is a valid value.
+///
+/// # Safety
+///
+/// Implementors must guarantee that a value consisting entirely of zero
+/// bytes is a valid, initialized instance of the type.
+pub unsafe trait Zeroable: Copy {}
+
+macro_rules! impl_zeroable {
+	($($t:ty),*) => {
+		$(unsafe impl Zeroable for $t {})*
+	};
+}
+
+impl_zeroable!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+pub trait SeqSink<T> {
+	fn push(&mut self, item: T);
+	fn len(&self) -> usize;
+
+	fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
 pub struct ArrayList<T> {
 	buf: *mut T,
 	buf_extents: usize,
 	len: usize,
+	auto_shrink: bool,
 }
 
 impl<T> ArrayList<T> {
 	pub fn new() -> ArrayList<T> {
-		ArrayList {
-			buf: unsafe { alloc::alloc(Self::layout()) as *mut T },
-			buf_extents: 1,
-			len: 0,
-		}
+		Self::with_capacity(0)
 	}
 
 	pub fn with_capacity(cap: usize) -> ArrayList<T> {
-		let mut buf_extents = cap / EXTENT_LEN;
-		if cap % EXTENT_LEN > 0 {
-			buf_extents += 1;
-		}
-		ArrayList {
-			buf: unsafe {
-				alloc::realloc(
-					alloc::alloc(Self::layout()),
-					Self::layout(),
-					Self::layout().size() * buf_extents,
-				) as *mut T
-			},
-			buf_extents,
+		let buf_extents = extents_for(cap);
+		let mut arraylist = ArrayList {
+			buf: ptr::NonNull::dangling().as_ptr(),
+			buf_extents: 0,
 			len: 0,
+			auto_shrink: true,
+		};
+		arraylist.realloc_extents(buf_extents);
+		arraylist
+	}
+
+	/// Like `with_capacity`, but returns an error instead of panicking or
+	/// aborting when the capacity overflows or the allocation fails.
+	pub fn try_with_capacity(cap: usize) -> Result<ArrayList<T>, TryReserveError> {
+		let mut arraylist = Self::new();
+		arraylist.try_reserve_exact(cap)?;
+		Ok(arraylist)
+	}
+
+	pub fn from_exact<I>(iter: I) -> ArrayList<T>
+	where
+		I: IntoIterator<Item = T>,
+		I::IntoIter: ExactSizeIterator,
+	{
+		let iter = iter.into_iter();
+		let mut arraylist = Self::with_capacity(iter.len());
+		for item in iter {
+			arraylist.push(item);
+		}
+		arraylist
+	}
+
+	/// Like `From<&[T]>`, but copies the whole slice with a single memcpy.
+	pub fn from_copy_slice(s: &[T]) -> ArrayList<T>
+	where
+		T: Copy,
+	{
+		let mut arraylist = Self::with_capacity(s.len());
+		unsafe {
+			ptr::copy_nonoverlapping(s.as_ptr(), arraylist.buf, s.len());
 		}
+		arraylist.len = s.len();
+		arraylist
+	}
+
+	/// Allocates room for at least `cap` elements. Storage is allocated in
+	/// whole extents, so `capacity()` is `capacity_for(cap)`, which may be
+	/// greater than `cap`.
+	pub fn with_capacity_exact(cap: usize) -> ArrayList<T> {
+		Self::with_capacity(cap)
+	}
+
+	/// Returns the capacity a list created with `with_capacity(cap)` will have.
+	pub fn capacity_for(cap: usize) -> usize {
+		extents_for(cap).saturating_mul(EXTENT_LEN)
 	}
 
 	pub fn len(&self) -> usize {
 		self.len
 	}
 
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
 	pub fn capacity(&self) -> usize {
-		self.buf_extents * EXTENT_LEN
+		// Only zero-sized types can hold enough extents for this to saturate.
+		self.buf_extents.saturating_mul(EXTENT_LEN)
 	}
 
-	pub fn clear(&mut self) {
-		for i in 0..self.len {
-			unsafe {
-				ptr::drop_in_place(self.buf.add(i));
+	pub fn extents(&self) -> usize {
+		self.buf_extents
+	}
+
+	pub fn bytes_allocated(&self) -> usize {
+		Self::extents_size(self.buf_extents)
+	}
+
+	pub fn reserve(&mut self, additional: usize) {
+		let len = self.len.checked_add(additional).expect("capacity overflow");
+		self.reserve_extents(extents_for(len));
+	}
+
+	pub fn reserve_extents(&mut self, extents: usize) {
+		if self.buf_extents < extents {
+			self.realloc_extents(extents);
+		}
+	}
+
+	pub fn reserve_for<F: FnOnce(&mut Self)>(&mut self, additional: usize, f: F) {
+		self.reserve(additional);
+		let extents = self.buf_extents;
+		f(self);
+		debug_assert!(
+			self.buf_extents == extents,
+			"Reallocation inside reserve_for region"
+		);
+	}
+
+	pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+		let len = self
+			.len
+			.checked_add(additional)
+			.ok_or(TryReserveError::CapacityOverflow)?;
+		let extents = extents_for(len);
+		if self.buf_extents < extents {
+			self.try_realloc_extents(extents)?;
+		}
+		Ok(())
+	}
+
+	pub fn shrink_to_fit(&mut self) {
+		let extents = self.required_extents();
+		if self.buf_extents > extents {
+			self.realloc_extents(extents);
+		}
+	}
+
+	pub fn at(&self, index: usize) -> Result<&T, IndexError> {
+		if index < self.len {
+			Ok(&self[index])
+		} else {
+			Err(IndexError {
+				index,
+				len: self.len,
+			})
+		}
+	}
+
+	pub fn first(&self) -> Option<&T> {
+		self.as_slice().first()
+	}
+
+	pub fn first_mut(&mut self) -> Option<&mut T> {
+		self.as_mut_slice().first_mut()
+	}
+
+	pub fn last(&self) -> Option<&T> {
+		self.as_slice().last()
+	}
+
+	pub fn last_mut(&mut self) -> Option<&mut T> {
+		self.as_mut_slice().last_mut()
+	}
+
+	pub fn get_many_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut T; N]> {
+		for (i, &index) in indices.iter().enumerate() {
+			if index >= self.len || indices[..i].contains(&index) {
+				return None;
 			}
 		}
-		self.shrink(self.len);
+		let buf = self.buf;
+		Some(std::array::from_fn(|i| unsafe {
+			&mut *buf.add(indices[i])
+		}))
+	}
+
+	pub fn get_rel(&self, index: isize) -> Option<&T> {
+		let index = if index < 0 {
+			self.len.checked_sub(index.unsigned_abs())?
+		} else {
+			index as usize
+		};
+		self.as_slice().get(index)
+	}
+
+	pub fn set_auto_shrink(&mut self, enabled: bool) {
+		self.auto_shrink = enabled;
+	}
+
+	pub fn clear(&mut self) {
+		let len = self.len;
+		self.len = 0;
+		unsafe {
+			ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.buf, len));
+		}
+		self.shrink_extents();
+	}
+
+	/// Drops all elements like `clear`, but always keeps the allocated capacity,
+	/// regardless of the auto-shrink setting.
+	pub fn reset(&mut self) {
+		let len = self.len;
+		self.len = 0;
+		unsafe {
+			ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.buf, len));
+		}
+	}
+
+	/// Resets the list and hands it back, keeping its capacity for reuse.
+	pub fn recycle(mut self) -> ArrayList<T> {
+		self.reset();
+		self
+	}
+
+	/// Sets the length of the list without dropping or initializing anything.
+	///
+	/// # Safety
+	///
+	/// `new_len` must not exceed `capacity()`, and the elements at
+	/// `0..new_len` must be initialized.
+	pub unsafe fn set_len(&mut self, new_len: usize) {
+		debug_assert!(new_len <= self.capacity());
+		self.len = new_len;
+	}
+
+	/// Runs the destructor of every element in `range` in place, without
+	/// changing the length or moving any other element.
+	///
+	/// # Safety
+	///
+	/// Every slot in `range` must hold an initialized element, and after the
+	/// call those slots must be treated as uninitialized: they must be
+	/// overwritten (e.g. with `ptr::write`) or excluded from the list with
+	/// `set_len` before the list is read, modified or dropped again.
+	pub unsafe fn drop_range(&mut self, range: Range<usize>) {
+		if range.start > range.end || range.end > self.capacity() {
+			panic!("Index out of bounds");
+		}
+		ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+			self.buf.add(range.start),
+			range.end - range.start,
+		));
 	}
 
 	pub fn insert(&mut self, index: usize, item: T) {
@@ -61,264 +293,3530 @@ impl<T> ArrayList<T> {
 			panic!("Index out of bounds");
 		}
 		self.grow(1);
+		debug_assert!(index < self.len);
+		debug_assert!(index < self.capacity());
 		unsafe {
 			ptr::copy(
 				self.buf.add(index),
 				self.buf.add(index + 1),
-				self.len - index,
+				self.len - index - 1,
 			);
+			ptr::write(self.buf.add(index), item);
 		}
-		self[index] = item;
 	}
 
 	pub fn push(&mut self, item: T) {
 		self.grow(1);
 		let last_idx = self.len - 1;
-		self[last_idx] = item;
+		debug_assert!(last_idx < self.capacity());
+		unsafe {
+			ptr::write(self.buf.add(last_idx), item);
+		}
 	}
 
-	pub fn remove(&mut self, index: usize) -> T {
-		if index >= self.len {
-			panic!("Index out of bounds");
+	/// Appends `item` without checking capacity or reallocating.
+	///
+	/// # Safety
+	///
+	/// `len()` must be less than `capacity()`, e.g. after a sufficient
+	/// `reserve`.
+	pub unsafe fn push_unchecked(&mut self, item: T) {
+		debug_assert!(self.len < self.capacity());
+		ptr::write(self.buf.add(self.len), item);
+		self.len += 1;
+	}
+
+	pub fn push_tracked(&mut self, item: T) -> bool {
+		let extents = self.buf_extents;
+		self.push(item);
+		self.buf_extents != extents
+	}
+
+	pub fn extend_from_slice(&mut self, s: &[T])
+	where
+		T: Clone,
+	{
+		self.reserve(s.len());
+		for item in s.iter() {
+			self.push(item.clone());
 		}
-		let item = unsafe {
-			let mut space = MaybeUninit::<T>::uninit();
-			ptr::copy_nonoverlapping(self.buf.add(index), space.as_mut_ptr(), 1);
-			ptr::copy(
-				self.buf.add(index + 1),
-				self.buf.add(index),
-				self.len - index - 1,
-			);
-			space.assume_init()
-		};
-		self.shrink(1);
-		item
 	}
 
-	fn grow(&mut self, count: usize) {
-		self.len += count;
-		let extents = self.required_extents();
-		if self.buf_extents < extents {
-			self.realloc_extents(extents);
+	pub fn fill_from_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) -> I::IntoIter {
+		let mut iter = iter.into_iter();
+		while self.len < self.capacity() {
+			match iter.next() {
+				Some(item) => {
+					unsafe {
+						ptr::write(self.buf.add(self.len), item);
+					}
+					self.len += 1;
+				}
+				None => break,
+			}
 		}
+		iter
 	}
 
-	fn shrink(&mut self, count: usize) {
-		self.len -= count;
-		let extents = self.required_extents();
-		if self.buf_extents > extents {
-			self.realloc_extents(extents);
+	pub fn append_bounded<I: IntoIterator<Item = T>>(
+		&mut self,
+		iter: I,
+		max_len: usize,
+	) -> I::IntoIter {
+		let mut iter = iter.into_iter();
+		while self.len < max_len {
+			match iter.next() {
+				Some(item) => self.push(item),
+				None => break,
+			}
 		}
+		iter
 	}
 
-	fn required_extents(&self) -> usize {
-		let extents = self.len / EXTENT_LEN;
-		if self.len % EXTENT_LEN > 0 {
-			extents + 1
+	pub fn resize_zeroed(&mut self, new_len: usize)
+	where
+		T: Zeroable,
+	{
+		if new_len < self.len {
+			self.shrink(self.len - new_len);
 		} else {
-			extents
+			let len = self.len;
+			self.grow(new_len - len);
+			unsafe {
+				ptr::write_bytes(self.buf.add(len), 0, new_len - len);
+			}
 		}
 	}
 
-	fn realloc_extents(&mut self, extents: usize) {
-		self.buf_extents = extents;
-		self.buf = unsafe {
-			alloc::realloc(
-				self.buf as *mut u8,
-				Self::layout(),
-				Self::layout().size() * self.buf_extents,
-			) as *mut T
+	pub fn push_n<F: FnMut(usize) -> T>(&mut self, count: usize, mut f: F) {
+		self.reserve(count);
+		for i in 0..count {
+			unsafe {
+				ptr::write(self.buf.add(self.len), f(i));
+			}
+			self.len += 1;
+		}
+	}
+
+	pub fn map_in_place<F: FnMut(T) -> T>(&mut self, mut f: F) {
+		let mut guard = MapGuard {
+			len: self.len,
+			index: 0,
+			list: self,
 		};
+		guard.list.len = 0;
+		while guard.index < guard.len {
+			unsafe {
+				let item = guard.list.buf.add(guard.index);
+				ptr::write(item, f(ptr::read(item)));
+			}
+			guard.index += 1;
+		}
 	}
 
-	fn layout() -> Layout {
-		Layout::array::<T>(EXTENT_LEN).unwrap().pad_to_align()
+	/// Moves every element out, front to back, into `f`, leaving the list
+	/// empty. If `f` panics, the elements not yet passed to it are dropped.
+	pub fn drain_each<F: FnMut(T)>(&mut self, mut f: F) {
+		let mut guard = DrainEachGuard {
+			len: self.len,
+			index: 0,
+			list: self,
+		};
+		guard.list.len = 0;
+		while guard.index < guard.len {
+			let item = unsafe { ptr::read(guard.list.buf.add(guard.index)) };
+			guard.index += 1;
+			f(item);
+		}
 	}
-}
 
-impl<T> Drop for ArrayList<T> {
-	fn drop(&mut self) {
-		unsafe {
-			alloc::dealloc(self.buf as *mut u8, Self::layout());
+	pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+		self.retain_range(.., f);
+	}
+
+	/// Like `retain`, but only elements within `range` are tested and possibly
+	/// removed. Elements outside it are kept and keep their order.
+	pub fn retain_range<R: RangeBounds<usize>, F: FnMut(&T) -> bool>(
+		&mut self,
+		range: R,
+		mut f: F,
+	) {
+		let start = match range.start_bound() {
+			Bound::Included(&i) => i,
+			Bound::Excluded(&i) => i.checked_add(1).expect("Index out of bounds"),
+			Bound::Unbounded => 0,
+		};
+		let end = match range.end_bound() {
+			Bound::Included(&i) => i.checked_add(1).expect("Index out of bounds"),
+			Bound::Excluded(&i) => i,
+			Bound::Unbounded => self.len,
+		};
+		if start > end || end > self.len {
+			panic!("Index out of bounds");
+		}
+		let mut guard = RetainGuard {
+			original_len: self.len,
+			processed: start,
+			deleted: 0,
+			list: self,
+		};
+		guard.list.len = 0;
+		while guard.processed < end {
+			unsafe {
+				let item = guard.list.buf.add(guard.processed);
+				if f(&*item) {
+					if guard.deleted > 0 {
+						ptr::copy_nonoverlapping(item, item.sub(guard.deleted), 1);
+					}
+					guard.processed += 1;
+				} else {
+					guard.processed += 1;
+					guard.deleted += 1;
+					ptr::drop_in_place(item);
+				}
+			}
 		}
+		drop(guard);
+		self.shrink_extents();
 	}
-}
 
-impl<T: Clone> From<&[T]> for ArrayList<T> {
-	fn from(s: &[T]) -> ArrayList<T> {
-		let mut arraylist = Self::with_capacity(s.len());
-		for item in s.iter() {
-			arraylist.push(item.clone());
+	pub fn interleave(&self, other: &[T]) -> ArrayList<T>
+	where
+		T: Clone,
+	{
+		let a = self.as_slice();
+		let mut arraylist = ArrayList::with_capacity(a.len() + other.len());
+		for (x, y) in a.iter().zip(other) {
+			arraylist.push(x.clone());
+			arraylist.push(y.clone());
 		}
+		let common = a.len().min(other.len());
+		arraylist.extend_from_slice(&a[common..]);
+		arraylist.extend_from_slice(&other[common..]);
 		arraylist
 	}
-}
 
-impl<T> Index<usize> for ArrayList<T> {
-	type Output = T;
+	/// Keeps only the first occurrence of each element, preserving order.
+	///
+	/// Unlike `dedup`, duplicates don't have to be adjacent. Each distinct
+	/// element is cloned once into the set of values already seen.
+	pub fn retain_unique(&mut self)
+	where
+		T: Hash + Eq + Clone,
+	{
+		let mut seen = HashSet::new();
+		self.retain(|item| {
+			if seen.contains(item) {
+				false
+			} else {
+				seen.insert(item.clone());
+				true
+			}
+		});
+	}
 
-	fn index(&self, index: usize) -> &Self::Output {
-		unsafe {
-			if index >= self.len {
-				panic!("Index out of bounds");
+	pub fn merge_sorted(&mut self, other: &[T])
+	where
+		T: Ord + Clone,
+	{
+		self.reserve(other.len());
+		let mut guard = MergeGuard {
+			i: self.len,
+			j: other.len(),
+			total: self.len + other.len(),
+			list: self,
+		};
+		// Merges backwards into the reserved tail. The list's own elements stay
+		// in `0..i`, merged ones fill `i + j..total`, and the guard closes the
+		// gap between them if a comparison or clone panics.
+		guard.list.len = 0;
+		while guard.j > 0 {
+			let k = guard.i + guard.j - 1;
+			unsafe {
+				if guard.i > 0 && *guard.list.buf.add(guard.i - 1) > other[guard.j - 1] {
+					ptr::copy_nonoverlapping(
+						guard.list.buf.add(guard.i - 1),
+						guard.list.buf.add(k),
+						1,
+					);
+					guard.i -= 1;
+				} else {
+					ptr::write(guard.list.buf.add(k), other[guard.j - 1].clone());
+					guard.j -= 1;
+				}
 			}
-			&(*(self.buf.add(index)))
 		}
 	}
-}
 
-impl<T> IndexMut<usize> for ArrayList<T> {
-	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-		unsafe {
-			if index >= self.len {
-				panic!("Index out of bounds");
+	pub fn dedup(&mut self)
+	where
+		T: PartialEq,
+	{
+		self.dedup_by(|a, b| a == b);
+	}
+
+	pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+		if self.len < 2 {
+			return;
+		}
+		let mut guard = RetainGuard {
+			original_len: self.len,
+			processed: 1,
+			deleted: 0,
+			list: self,
+		};
+		guard.list.len = 0;
+		while guard.processed < guard.original_len {
+			unsafe {
+				let item = guard.list.buf.add(guard.processed);
+				let kept = item.sub(guard.deleted + 1);
+				if same_bucket(&mut *item, &mut *kept) {
+					guard.processed += 1;
+					guard.deleted += 1;
+					ptr::drop_in_place(item);
+				} else {
+					if guard.deleted > 0 {
+						ptr::copy_nonoverlapping(item, item.sub(guard.deleted), 1);
+					}
+					guard.processed += 1;
+				}
 			}
-			&mut (*(self.buf.add(index)))
 		}
+		drop(guard);
+		self.shrink_extents();
 	}
-}
 
-impl<T: PartialEq> PartialEq for ArrayList<T> {
-	fn eq(&self, other: &Self) -> bool {
-		let mut equal = self.len() == other.len();
-		let mut i = 0;
-		let len = self.len();
-		while equal && i < len {
-			if self[i] != other[i] {
-				equal = false;
+	pub fn dedup_keep_last(&mut self)
+	where
+		T: PartialEq,
+	{
+		if self.len < 2 {
+			return;
+		}
+		let mut guard = RetainGuard {
+			original_len: self.len,
+			processed: 1,
+			deleted: 0,
+			list: self,
+		};
+		guard.list.len = 0;
+		while guard.processed < guard.original_len {
+			unsafe {
+				let item = guard.list.buf.add(guard.processed);
+				let kept = item.sub(guard.deleted + 1);
+				if *item == *kept {
+					let replaced = ptr::read(kept);
+					ptr::copy_nonoverlapping(item, kept, 1);
+					guard.processed += 1;
+					guard.deleted += 1;
+					drop(replaced);
+				} else {
+					if guard.deleted > 0 {
+						ptr::copy_nonoverlapping(item, item.sub(guard.deleted), 1);
+					}
+					guard.processed += 1;
+				}
 			}
-			i += 1;
 		}
-		equal
+		drop(guard);
+		self.shrink_extents();
 	}
-}
-
-impl<T: Debug> Debug for ArrayList<T> {
-	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-		fmt.debug_struct(&("ArrayList<".to_owned() + std::any::type_name::<T>() + ">"))
-			.field("len", &self.len)
-			.field("buf_extents", &self.buf_extents)
-			.finish()?;
 
-		fmt.write_str(" ")?;
+	pub fn sort_dedup(&mut self)
+	where
+		T: Ord,
+	{
+		self.sort();
+		self.dedup();
+	}
 
-		let mut dbg = fmt.debug_list();
-		for i in 0..self.len() {
-			dbg.entry(&self[i]);
+	/// Pushes `item` and, if that takes the list past `max` elements, removes
+	/// and returns the oldest one from the front.
+	///
+	/// A list that already holds more than `max` elements is first trimmed to
+	/// `max` by dropping its oldest elements; only the element evicted to
+	/// make room for `item` is returned.
+	pub fn push_bounded(&mut self, item: T, max: usize) -> Option<T> {
+		if self.len > max {
+			let surplus = self.len - max;
+			self.retain_range(..surplus, |_| false);
+		}
+		if self.len == max && max > 0 {
+			// Rotate in place rather than push and remove, which would grow
+			// into a new extent and shrink back out of it when `max` is a
+			// multiple of the extent length.
+			unsafe {
+				let oldest = ptr::read(self.buf);
+				ptr::copy(self.buf.add(1), self.buf, self.len - 1);
+				ptr::write(self.buf.add(self.len - 1), item);
+				return Some(oldest);
+			}
+		}
+		self.push(item);
+		if self.len > max {
+			Some(self.remove(0))
+		} else {
+			None
 		}
-		dbg.finish()
 	}
-}
 
-#[cfg(test)]
-mod tests {
-	use super::*;
+	pub fn replace(&mut self, index: usize, value: T) -> T {
+		mem::replace(&mut self[index], value)
+	}
 
-	#[test]
-	fn i32_new() {
-		let a = ArrayList::<i32>::new();
-		assert_eq!(a.len(), 0);
+	pub fn remove(&mut self, index: usize) -> T {
+		if index >= self.len {
+			panic!("Index out of bounds");
+		}
+		let item = unsafe {
+			let mut space = MaybeUninit::<T>::uninit();
+			ptr::copy_nonoverlapping(self.buf.add(index), space.as_mut_ptr(), 1);
+			ptr::copy(
+				self.buf.add(index + 1),
+				self.buf.add(index),
+				self.len - index - 1,
+			);
+			space.assume_init()
+		};
+		self.poison_last();
+		self.shrink(1);
+		item
 	}
 
-	#[test]
-	fn i32_with_capacity() {
-		let a = ArrayList::<i32>::with_capacity(34);
-		assert_eq!(a.len(), 0);
-		assert_eq!(a.capacity(), 48);
+	pub fn pop(&mut self) -> Option<T> {
+		if self.len == 0 {
+			return None;
+		}
+		let item = unsafe { ptr::read(self.buf.add(self.len - 1)) };
+		self.poison_last();
+		self.shrink(1);
+		Some(item)
 	}
 
-	#[test]
-	fn i32_from_slice() {
-		let a = ArrayList::from(&[] as &[i32]);
-		assert_eq!(a, ArrayList::new());
+	pub fn swap_remove(&mut self, index: usize) -> T {
+		if index >= self.len {
+			panic!("Index out of bounds");
+		}
+		let item = unsafe {
+			let item = ptr::read(self.buf.add(index));
+			ptr::copy(self.buf.add(self.len - 1), self.buf.add(index), 1);
+			item
+		};
+		self.poison_last();
+		self.shrink(1);
+		item
 	}
 
-	#[test]
+	pub fn swap_remove_front(&mut self) -> Option<T> {
+		if self.len == 0 {
+			None
+		} else {
+			Some(self.swap_remove(0))
+		}
+	}
+
+	pub fn remove_tracked(&mut self, index: usize) -> (T, bool) {
+		let extents = self.buf_extents;
+		let item = self.remove(index);
+		(item, self.buf_extents != extents)
+	}
+
+	pub fn drain_sorted(&mut self) -> IntoIter<T>
+	where
+		T: Ord,
+	{
+		self.sort();
+		let mut empty = Self::with_capacity(0);
+		empty.auto_shrink = self.auto_shrink;
+		mem::replace(self, empty).into_iter()
+	}
+
+	/// Consumes the list, yielding each element by value. Whether the
+	/// iterator is exhausted or dropped early, the remaining elements are
+	/// dropped and the buffer is freed exactly once.
+	pub fn into_drain(self) -> impl Iterator<Item = T> {
+		self.into_iter()
+	}
+
+	pub fn into_iter_from(mut self, start: usize) -> IntoIter<T> {
+		if start > self.len {
+			panic!("Index out of bounds");
+		}
+		let len = self.len;
+		self.len = 0;
+		let iter = IntoIter {
+			list: self,
+			front: start,
+			back: len,
+		};
+		unsafe {
+			ptr::drop_in_place(ptr::slice_from_raw_parts_mut(iter.list.buf, start));
+		}
+		iter
+	}
+
+	pub fn into_chunks(self, size: usize) -> IntoChunks<T> {
+		if size == 0 {
+			panic!("Chunk size must be non-zero");
+		}
+		IntoChunks {
+			iter: self.into_iter(),
+			size,
+		}
+	}
+
+	pub fn as_slice(&self) -> &[T] {
+		unsafe { slice::from_raw_parts(self.buf, self.len) }
+	}
+
+	pub fn as_mut_slice(&mut self) -> &mut [T] {
+		unsafe { slice::from_raw_parts_mut(self.buf, self.len) }
+	}
+
+	pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+		IterMut {
+			buf: self.buf,
+			front: 0,
+			back: self.len,
+			marker: PhantomData,
+		}
+	}
+
+	pub fn iter_mut_indexed(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+		self.iter_mut().enumerate()
+	}
+
+	pub fn cursor(&self) -> Cursor<'_, T> {
+		Cursor {
+			items: self.as_slice(),
+			index: 0,
+		}
+	}
+
+	pub fn iter_step(&self, step: usize) -> impl Iterator<Item = &T> {
+		if step == 0 {
+			panic!("Step must be non-zero");
+		}
+		self.as_slice().iter().step_by(step)
+	}
+
+	pub fn chunks_mut(&mut self, size: usize) -> ChunksMut<'_, T> {
+		if size == 0 {
+			panic!("Chunk size must be non-zero");
+		}
+		ChunksMut {
+			rest: self.as_mut_slice(),
+			size,
+		}
+	}
+
+	pub fn pairwise(&self) -> impl Iterator<Item = (&T, &T)> {
+		self.as_slice().windows(2).map(|pair| (&pair[0], &pair[1]))
+	}
+
+	pub fn windows_mut(&mut self, size: usize, mut f: impl FnMut(&mut [T])) {
+		if size == 0 {
+			panic!("Window size must be non-zero");
+		}
+		let s = self.as_mut_slice();
+		for start in 0..s.len().saturating_sub(size - 1) {
+			f(&mut s[start..start + size]);
+		}
+	}
+
+	pub fn as_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+		if N == 0 {
+			panic!("Chunk size must be non-zero");
+		}
+		let chunks = self.len / N;
+		unsafe {
+			(
+				slice::from_raw_parts(self.buf as *const [T; N], chunks),
+				slice::from_raw_parts(self.buf.add(chunks * N), self.len - chunks * N),
+			)
+		}
+	}
+
+	pub fn into_array<const N: usize>(mut self) -> Result<[T; N], ArrayList<T>> {
+		if self.len != N {
+			return Err(self);
+		}
+		self.len = 0;
+		Ok(unsafe { ptr::read(self.buf as *const [T; N]) })
+	}
+
+	pub fn freeze(self) -> FrozenList<T> {
+		FrozenList::new(Arc::from(self))
+	}
+
+	/// Converts the list into a `Vec` without copying or reallocating.
+	///
+	/// An allocation of `n` extents has the same layout as a `Vec` of
+	/// capacity `n * EXTENT_LEN`, so the buffer is handed over as is and the
+	/// resulting `Vec`'s capacity equals `capacity()`. Zero-sized element
+	/// types and unallocated lists produce a `Vec` with its own capacity.
+	pub fn into_vec(self) -> Vec<T> {
+		if self.bytes_allocated() == 0 {
+			return self.into_iter().collect();
+		}
+		let arraylist = ManuallyDrop::new(self);
+		unsafe { Vec::from_raw_parts(arraylist.buf, arraylist.len, arraylist.capacity()) }
+	}
+
+	pub fn to_vec(&self) -> Vec<T>
+	where
+		T: Clone,
+	{
+		self.as_slice().to_vec()
+	}
+
+	pub fn clone_into_vec(&self, out: &mut Vec<T>)
+	where
+		T: Clone,
+	{
+		out.clear();
+		out.extend_from_slice(self.as_slice());
+	}
+
+	pub fn swap_ends(&mut self) {
+		if self.len < 2 {
+			return;
+		}
+		unsafe {
+			ptr::swap(self.buf, self.buf.add(self.len - 1));
+		}
+	}
+
+	pub fn swap_with_slice(&mut self, other: &mut [T]) {
+		if other.len() != self.len {
+			panic!("Slice length mismatch");
+		}
+		self.as_mut_slice().swap_with_slice(other);
+	}
+
+	pub fn copied(&self) -> ArrayList<T>
+	where
+		T: Copy,
+	{
+		Self::from_copy_slice(self.as_slice())
+	}
+
+	pub fn cloned(&self) -> ArrayList<T>
+	where
+		T: Clone,
+	{
+		Self::from(self.as_slice())
+	}
+
+	pub fn take_while_collect<F: FnMut(&T) -> bool>(&self, mut pred: F) -> ArrayList<T>
+	where
+		T: Clone,
+	{
+		self.as_slice()
+			.iter()
+			.take_while(|item| pred(item))
+			.collect()
+	}
+
+	pub fn skip_while_collect<F: FnMut(&T) -> bool>(&self, mut pred: F) -> ArrayList<T>
+	where
+		T: Clone,
+	{
+		self.as_slice()
+			.iter()
+			.skip_while(|item| pred(item))
+			.collect()
+	}
+
+	/// Sums the elements by reference. `S` is the result type and must
+	/// implement `Sum<&T>`, as the primitive numeric types do for themselves.
+	pub fn sum<'a, S: std::iter::Sum<&'a T>>(&'a self) -> S {
+		self.as_slice().iter().sum()
+	}
+
+	/// Multiplies the elements by reference. `P` must implement `Product<&T>`.
+	pub fn product<'a, P: std::iter::Product<&'a T>>(&'a self) -> P {
+		self.as_slice().iter().product()
+	}
+
+	pub fn count_if<F: FnMut(&T) -> bool>(&self, mut pred: F) -> usize {
+		self.as_slice().iter().filter(|item| pred(item)).count()
+	}
+
+	pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+		self.as_slice().iter().fold(init, f)
+	}
+
+	pub fn for_each<F: FnMut(&T)>(&self, f: F) {
+		self.as_slice().iter().for_each(f);
+	}
+
+	pub fn eq_by<U, F: FnMut(&T, &U) -> bool>(&self, other: &[U], mut f: F) -> bool {
+		self.len == other.len() && self.as_slice().iter().zip(other).all(|(a, b)| f(a, b))
+	}
+
+	pub fn position<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<usize> {
+		self.as_slice().iter().position(pred)
+	}
+
+	pub fn rposition<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<usize> {
+		self.as_slice().iter().rposition(pred)
+	}
+
+	pub fn min_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<&T> {
+		self.as_slice().iter().min_by_key(|item| f(item))
+	}
+
+	pub fn max_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<&T> {
+		self.as_slice().iter().max_by_key(|item| f(item))
+	}
+
+	pub fn argmax(&self) -> Option<usize>
+	where
+		T: PartialOrd,
+	{
+		self.arg_best(|item, best| item > best)
+	}
+
+	pub fn argmin(&self) -> Option<usize>
+	where
+		T: PartialOrd,
+	{
+		self.arg_best(|item, best| item < best)
+	}
+
+	/// Returns a `Debug` view that also shows capacity and extent counts.
+	///
+	/// Spare capacity is never read; it is summarised as a count of
+	/// uninitialised slots after the live elements.
+	pub fn debug_verbose(&self) -> impl Debug + '_
+	where
+		T: Debug,
+	{
+		DebugVerbose(self)
+	}
+
+	pub fn sort(&mut self)
+	where
+		T: Ord,
+	{
+		self.as_mut_slice().sort();
+	}
+
+	pub fn sort_by_cached_key<K: Ord, F: FnMut(&T) -> K>(&mut self, f: F) {
+		self.as_mut_slice().sort_by_cached_key(f);
+	}
+
+	/// Inserts `item` into an already sorted list, after any equal elements,
+	/// and returns the index it was placed at.
+	pub fn insert_sorted(&mut self, item: T) -> usize
+	where
+		T: Ord,
+	{
+		let index = self.as_slice().partition_point(|x| *x <= item);
+		self.insert(index, item);
+		index
+	}
+
+	pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+	where
+		T: Ord,
+	{
+		self.as_slice().binary_search(x)
+	}
+
+	pub fn binary_search_by_key<B: Ord, F: FnMut(&T) -> B>(
+		&self,
+		b: &B,
+		f: F,
+	) -> Result<usize, usize> {
+		self.as_slice().binary_search_by_key(b, f)
+	}
+
+	pub fn is_sorted(&self) -> bool
+	where
+		T: PartialOrd,
+	{
+		self.is_sorted_by(|a, b| a <= b)
+	}
+
+	pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, mut f: F) -> bool {
+		self.as_slice().windows(2).all(|w| f(&w[0], &w[1]))
+	}
+
+	pub fn chunk_by<F: FnMut(&T, &T) -> bool>(&self, pred: F) -> ChunkBy<'_, T, F> {
+		ChunkBy {
+			rest: self.as_slice(),
+			pred,
+		}
+	}
+
+	pub fn run_length_encode(&self) -> ArrayList<(T, usize)>
+	where
+		T: Clone + PartialEq,
+	{
+		let mut runs = ArrayList::new();
+		for run in self.chunk_by(|a, b| a == b) {
+			runs.push((run[0].clone(), run.len()));
+		}
+		runs
+	}
+
+	pub fn contains_all(&self, items: &[T]) -> bool
+	where
+		T: PartialEq,
+	{
+		items.iter().all(|item| self.as_slice().contains(item))
+	}
+
+	pub fn find_subslice(&self, needle: &[T]) -> Option<usize>
+	where
+		T: PartialEq,
+	{
+		if needle.len() > self.len {
+			return None;
+		}
+		(0..=self.len - needle.len()).find(|&i| {
+			needle
+				.iter()
+				.enumerate()
+				.all(|(j, item)| self[i + j] == *item)
+		})
+	}
+
+	pub fn contains_subslice(&self, needle: &[T]) -> bool
+	where
+		T: PartialEq,
+	{
+		self.find_subslice(needle).is_some()
+	}
+
+	// Overwrites the last slot, whose element has just been moved out, so that
+	// stale reads in debug builds see garbage instead of a plausible value.
+	fn poison_last(&mut self) {
+		#[cfg(debug_assertions)]
+		unsafe {
+			ptr::write_bytes(
+				self.buf.add(self.len - 1) as *mut u8,
+				0xa5,
+				mem::size_of::<T>(),
+			);
+		}
+	}
+
+	fn grow(&mut self, count: usize) {
+		self.len = self.len.checked_add(count).expect("capacity overflow");
+		let extents = self.required_extents();
+		if self.buf_extents < extents {
+			self.realloc_extents(extents);
+		}
+		debug_assert!(self.len <= self.capacity());
+	}
+
+	fn shrink(&mut self, count: usize) {
+		self.len -= count;
+		self.shrink_extents();
+	}
+
+	fn shrink_extents(&mut self) {
+		if !self.auto_shrink {
+			return;
+		}
+		let extents = self.required_extents();
+		if self.buf_extents > extents {
+			self.realloc_extents(extents);
+		}
+	}
+
+	fn required_extents(&self) -> usize {
+		extents_for(self.len)
+	}
+
+	fn realloc_extents(&mut self, extents: usize) {
+		match self.try_realloc_extents(extents) {
+			Ok(()) => {}
+			Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+			Err(TryReserveError::AllocError { layout }) => alloc::handle_alloc_error(layout),
+		}
+	}
+
+	fn try_realloc_extents(&mut self, extents: usize) -> Result<(), TryReserveError> {
+		let old_layout = Self::extents_layout(self.buf_extents);
+		let new_layout = Self::try_extents_layout(extents)?;
+		let buf = unsafe {
+			if new_layout.size() == 0 {
+				if old_layout.size() != 0 {
+					alloc::dealloc(self.buf as *mut u8, old_layout);
+				}
+				ptr::NonNull::dangling().as_ptr()
+			} else if old_layout.size() == 0 {
+				alloc::alloc(new_layout) as *mut T
+			} else {
+				alloc::realloc(self.buf as *mut u8, old_layout, new_layout.size()) as *mut T
+			}
+		};
+		if buf.is_null() {
+			return Err(TryReserveError::AllocError { layout: new_layout });
+		}
+		self.buf = buf;
+		self.buf_extents = extents;
+		Ok(())
+	}
+
+	fn extents_layout(extents: usize) -> Layout {
+		match Self::try_extents_layout(extents) {
+			Ok(layout) => layout,
+			Err(_) => panic!("capacity overflow"),
+		}
+	}
+
+	fn extents_size(extents: usize) -> usize {
+		Self::extents_layout(extents).size()
+	}
+
+	fn try_extents_layout(extents: usize) -> Result<Layout, TryReserveError> {
+		let layout = Self::layout();
+		match layout.size().checked_mul(extents) {
+			Some(size) if size <= isize::MAX as usize => {
+				Layout::from_size_align(size, layout.align())
+					.map_err(|_| TryReserveError::CapacityOverflow)
+			}
+			_ => Err(TryReserveError::CapacityOverflow),
+		}
+	}
+
+	fn arg_best<F: Fn(&T, &T) -> bool>(&self, better: F) -> Option<usize> {
+		let s = self.as_slice();
+		if s.is_empty() {
+			return None;
+		}
+		let mut best = 0;
+		for i in 1..s.len() {
+			if better(&s[i], &s[best]) {
+				best = i;
+			}
+		}
+		Some(best)
+	}
+
+	fn layout() -> Layout {
+		let elem = Layout::new::<T>().pad_to_align();
+		let size = elem
+			.size()
+			.checked_mul(EXTENT_LEN)
+			.expect("capacity overflow");
+		Layout::from_size_align(size, elem.align()).unwrap()
+	}
+}
+
+impl<T> ArrayList<ArrayList<T>> {
+	pub fn flatten(mut self) -> ArrayList<T> {
+		let total = self.fold(0, |acc, inner| acc + inner.len);
+		let mut arraylist = ArrayList::<T>::with_capacity(total);
+		for inner in self.iter_mut() {
+			unsafe {
+				ptr::copy_nonoverlapping(inner.buf, arraylist.buf.add(arraylist.len), inner.len);
+			}
+			arraylist.len += inner.len;
+			inner.len = 0;
+		}
+		arraylist
+	}
+}
+
+pub fn concat<T: Clone>(lists: &[ArrayList<T>]) -> ArrayList<T> {
+	let total = lists.iter().map(|list| list.len).sum();
+	let mut arraylist = ArrayList::with_capacity(total);
+	for list in lists.iter() {
+		for item in list.as_slice().iter() {
+			arraylist.push(item.clone());
+		}
+	}
+	arraylist
+}
+
+macro_rules! impl_sort_floats {
+	($($t:ty),*) => {
+		$(impl ArrayList<$t> {
+			/// Sorts by `total_cmp`, so negative NaNs come first and positive
+			/// NaNs last, with the ordinary values in between.
+			pub fn sort_floats(&mut self) {
+				self.as_mut_slice().sort_by(<$t>::total_cmp);
+			}
+		})*
+	};
+}
+
+impl_sort_floats!(f32, f64);
+
+impl ArrayList<u8> {
+	pub fn make_ascii_uppercase(&mut self) {
+		self.as_mut_slice().make_ascii_uppercase();
+	}
+
+	pub fn make_ascii_lowercase(&mut self) {
+		self.as_mut_slice().make_ascii_lowercase();
+	}
+
+	pub fn read_to_end<R: io::Read>(&mut self, reader: &mut R) -> io::Result<usize> {
+		let mut chunk = [0; EXTENT_LEN * 16];
+		let mut total = 0;
+		loop {
+			match reader.read(&mut chunk) {
+				Ok(0) => return Ok(total),
+				Ok(n) => {
+					self.extend_from_slice(&chunk[..n]);
+					total += n;
+				}
+				Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+				Err(e) => return Err(e),
+			}
+		}
+	}
+
+	pub fn to_hex(&self) -> String {
+		let mut out = String::with_capacity(self.len * 2);
+		for &byte in self.as_slice() {
+			out.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+			out.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+		}
+		out
+	}
+
+	pub fn from_hex(s: &str) -> Result<ArrayList<u8>, DecodeError> {
+		let input = s.as_bytes();
+		if !input.len().is_multiple_of(2) {
+			return Err(DecodeError::InvalidLength { len: input.len() });
+		}
+		let mut arraylist = ArrayList::with_capacity(input.len() / 2);
+		for (i, pair) in input.chunks_exact(2).enumerate() {
+			let digit = |offset: usize| {
+				(pair[offset] as char).to_digit(16).map(|d| d as u8).ok_or(
+					DecodeError::InvalidByte {
+						index: 2 * i + offset,
+						byte: pair[offset],
+					},
+				)
+			};
+			arraylist.push(digit(0)? << 4 | digit(1)?);
+		}
+		Ok(arraylist)
+	}
+
+	/// Encodes the bytes as standard, padded base64 (RFC 4648).
+	pub fn to_base64(&self) -> String {
+		let mut out = String::with_capacity(self.len.div_ceil(3) * 4);
+		for group in self.as_slice().chunks(3) {
+			let b = [
+				group[0],
+				*group.get(1).unwrap_or(&0),
+				*group.get(2).unwrap_or(&0),
+			];
+			let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+			for k in 0..4 {
+				if k <= group.len() {
+					out.push(BASE64_DIGITS[(n >> (18 - 6 * k)) & 0x3f] as char);
+				} else {
+					out.push('=');
+				}
+			}
+		}
+		out
+	}
+
+	/// Decodes standard, padded base64 (RFC 4648).
+	pub fn from_base64(s: &str) -> Result<ArrayList<u8>, DecodeError> {
+		let input = s.as_bytes();
+		if !input.len().is_multiple_of(4) {
+			return Err(DecodeError::InvalidLength { len: input.len() });
+		}
+		let mut arraylist = ArrayList::with_capacity(input.len() / 4 * 3);
+		for (i, group) in input.chunks_exact(4).enumerate() {
+			let last = i == input.len() / 4 - 1;
+			let padding = group.iter().rev().take_while(|&&c| c == b'=').count();
+			if padding > 2 || (padding > 0 && !last) {
+				let index = 4 * i + group.iter().position(|&c| c == b'=').unwrap();
+				return Err(DecodeError::InvalidByte { index, byte: b'=' });
+			}
+			let mut n = 0;
+			for (k, &c) in group[..4 - padding].iter().enumerate() {
+				let value =
+					BASE64_DIGITS
+						.iter()
+						.position(|&d| d == c)
+						.ok_or(DecodeError::InvalidByte {
+							index: 4 * i + k,
+							byte: c,
+						})?;
+				n |= value << (18 - 6 * k);
+			}
+			for k in 0..3 - padding {
+				arraylist.push((n >> (16 - 8 * k)) as u8);
+			}
+		}
+		Ok(arraylist)
+	}
+}
+
+struct RetainGuard<'a, T> {
+	list: &'a mut ArrayList<T>,
+	original_len: usize,
+	processed: usize,
+	deleted: usize,
+}
+
+impl<'a, T> Drop for RetainGuard<'a, T> {
+	fn drop(&mut self) {
+		unsafe {
+			if self.deleted > 0 {
+				ptr::copy(
+					self.list.buf.add(self.processed),
+					self.list.buf.add(self.processed - self.deleted),
+					self.original_len - self.processed,
+				);
+			}
+		}
+		self.list.len = self.original_len - self.deleted;
+	}
+}
+
+struct MergeGuard<'a, T> {
+	list: &'a mut ArrayList<T>,
+	i: usize,
+	j: usize,
+	total: usize,
+}
+
+impl<'a, T> Drop for MergeGuard<'a, T> {
+	fn drop(&mut self) {
+		let merged = self.i + self.j;
+		unsafe {
+			ptr::copy(
+				self.list.buf.add(merged),
+				self.list.buf.add(self.i),
+				self.total - merged,
+			);
+		}
+		self.list.len = self.total - self.j;
+	}
+}
+
+struct MapGuard<'a, T> {
+	list: &'a mut ArrayList<T>,
+	len: usize,
+	index: usize,
+}
+
+impl<'a, T> Drop for MapGuard<'a, T> {
+	fn drop(&mut self) {
+		if self.index < self.len {
+			unsafe {
+				ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+					self.list.buf.add(self.index + 1),
+					self.len - self.index - 1,
+				));
+			}
+		}
+		self.list.len = self.index;
+	}
+}
+
+struct DrainEachGuard<'a, T> {
+	list: &'a mut ArrayList<T>,
+	len: usize,
+	index: usize,
+}
+
+impl<'a, T> Drop for DrainEachGuard<'a, T> {
+	fn drop(&mut self) {
+		unsafe {
+			ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+				self.list.buf.add(self.index),
+				self.len - self.index,
+			));
+		}
+		self.list.shrink_extents();
+	}
+}
+
+fn extents_for(len: usize) -> usize {
+	let extents = len / EXTENT_LEN;
+	if !len.is_multiple_of(EXTENT_LEN) {
+		extents + 1
+	} else {
+		extents
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexError {
+	pub index: usize,
+	pub len: usize,
+}
+
+impl Display for IndexError {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			fmt,
+			"Index {} out of bounds for length {}",
+			self.index, self.len
+		)
+	}
+}
+
+impl Error for IndexError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+	InvalidLength { len: usize },
+	InvalidByte { index: usize, byte: u8 },
+}
+
+impl Display for DecodeError {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			DecodeError::InvalidLength { len } => write!(fmt, "Invalid input length {}", len),
+			DecodeError::InvalidByte { index, byte } => {
+				write!(fmt, "Invalid byte {:#04x} at index {}", byte, index)
+			}
+		}
+	}
+}
+
+impl Error for DecodeError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+	CapacityOverflow,
+	AllocError { layout: Layout },
+}
+
+impl Display for TryReserveError {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			TryReserveError::CapacityOverflow => fmt.write_str("capacity overflow"),
+			TryReserveError::AllocError { layout } => {
+				write!(fmt, "memory allocation of {} bytes failed", layout.size())
+			}
+		}
+	}
+}
+
+impl Error for TryReserveError {}
+
+pub struct IterMut<'a, T> {
+	buf: *mut T,
+	front: usize,
+	back: usize,
+	marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+	type Item = &'a mut T;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.front < self.back {
+			let item = unsafe { &mut *self.buf.add(self.front) };
+			self.front += 1;
+			Some(item)
+		} else {
+			None
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.back - self.front;
+		(len, Some(len))
+	}
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.front < self.back {
+			self.back -= 1;
+			Some(unsafe { &mut *self.buf.add(self.back) })
+		} else {
+			None
+		}
+	}
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub struct IntoIter<T> {
+	list: ArrayList<T>,
+	front: usize,
+	back: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+	type Item = T;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.front < self.back {
+			let item = unsafe { ptr::read(self.list.buf.add(self.front)) };
+			self.front += 1;
+			Some(item)
+		} else {
+			None
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.back - self.front;
+		(len, Some(len))
+	}
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.front < self.back {
+			self.back -= 1;
+			Some(unsafe { ptr::read(self.list.buf.add(self.back)) })
+		} else {
+			None
+		}
+	}
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> Drop for IntoIter<T> {
+	fn drop(&mut self) {
+		unsafe {
+			ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+				self.list.buf.add(self.front),
+				self.back - self.front,
+			));
+		}
+	}
+}
+
+pub struct Cursor<'a, T> {
+	items: &'a [T],
+	index: usize,
+}
+
+impl<'a, T> Cursor<'a, T> {
+	pub fn position(&self) -> usize {
+		self.index
+	}
+
+	pub fn peek(&self) -> Option<&'a T> {
+		self.items.get(self.index)
+	}
+
+	pub fn peek_next(&self) -> Option<&'a T> {
+		self.items.get(self.index + 1)
+	}
+
+	pub fn advance(&mut self) -> Option<&'a T> {
+		let item = self.items.get(self.index)?;
+		self.index += 1;
+		Some(item)
+	}
+
+	pub fn retreat(&mut self) -> Option<&'a T> {
+		if self.index == 0 {
+			return None;
+		}
+		self.index -= 1;
+		self.items.get(self.index)
+	}
+}
+
+pub struct ChunksMut<'a, T> {
+	rest: &'a mut [T],
+	size: usize,
+}
+
+impl<'a, T> Iterator for ChunksMut<'a, T> {
+	type Item = &'a mut [T];
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.rest.is_empty() {
+			return None;
+		}
+		let rest = mem::take(&mut self.rest);
+		let (chunk, rest) = rest.split_at_mut(self.size.min(rest.len()));
+		self.rest = rest;
+		Some(chunk)
+	}
+}
+
+pub struct IntoChunks<T> {
+	iter: IntoIter<T>,
+	size: usize,
+}
+
+impl<T> Iterator for IntoChunks<T> {
+	type Item = ArrayList<T>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let len = self.size.min(self.iter.len());
+		if len == 0 {
+			return None;
+		}
+		let mut chunk = ArrayList::with_capacity(len);
+		chunk.extend(self.iter.by_ref().take(len));
+		Some(chunk)
+	}
+}
+
+pub struct ChunkBy<'a, T, F> {
+	rest: &'a [T],
+	pred: F,
+}
+
+impl<'a, T, F: FnMut(&T, &T) -> bool> Iterator for ChunkBy<'a, T, F> {
+	type Item = &'a [T];
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.rest.is_empty() {
+			return None;
+		}
+		let mut len = 1;
+		while len < self.rest.len() && (self.pred)(&self.rest[len - 1], &self.rest[len]) {
+			len += 1;
+		}
+		let (chunk, rest) = self.rest.split_at(len);
+		self.rest = rest;
+		Some(chunk)
+	}
+}
+
+pub struct ArrayListBuilder<T> {
+	list: ArrayList<T>,
+	reserved: usize,
+}
+
+impl<T> ArrayListBuilder<T> {
+	pub fn with_capacity(cap: usize) -> ArrayListBuilder<T> {
+		ArrayListBuilder {
+			list: ArrayList::with_capacity(cap),
+			reserved: cap,
+		}
+	}
+
+	pub fn remaining(&self) -> usize {
+		self.reserved.saturating_sub(self.list.len())
+	}
+
+	pub fn push(&mut self, item: T) {
+		debug_assert!(
+			self.list.len() < self.reserved,
+			"Builder reservation exceeded"
+		);
+		self.list.push(item);
+	}
+
+	pub fn extend_from_slice(&mut self, s: &[T])
+	where
+		T: Clone,
+	{
+		debug_assert!(s.len() <= self.remaining(), "Builder reservation exceeded");
+		for item in s.iter() {
+			self.list.push(item.clone());
+		}
+	}
+
+	pub fn finish(self) -> ArrayList<T> {
+		self.list
+	}
+}
+
+impl<T> SeqSink<T> for ArrayList<T> {
+	fn push(&mut self, item: T) {
+		ArrayList::push(self, item);
+	}
+
+	fn len(&self) -> usize {
+		self.len
+	}
+}
+
+impl<T> Default for ArrayList<T> {
+	fn default() -> ArrayList<T> {
+		Self::new()
+	}
+}
+
+impl<T> Drop for ArrayList<T> {
+	fn drop(&mut self) {
+		unsafe {
+			ptr::drop_in_place(self.as_mut_slice());
+		}
+		let layout = Self::extents_layout(self.buf_extents);
+		if layout.size() != 0 {
+			unsafe {
+				alloc::dealloc(self.buf as *mut u8, layout);
+			}
+		}
+	}
+}
+
+impl<T: Clone> From<&[T]> for ArrayList<T> {
+	fn from(s: &[T]) -> ArrayList<T> {
+		let mut arraylist = Self::with_capacity(s.len());
+		for item in s.iter() {
+			arraylist.push(item.clone());
+		}
+		arraylist
+	}
+}
+
+/// Collects through `Extend`, then releases any whole extents left unused
+/// when the iterator yields fewer elements than its capped upper bound.
+impl<T> FromIterator<T> for ArrayList<T> {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> ArrayList<T> {
+		let mut arraylist = Self::with_capacity(0);
+		arraylist.extend(iter);
+		arraylist.shrink_to_fit();
+		arraylist
+	}
+}
+
+impl<'a, T: Clone + 'a> FromIterator<&'a T> for ArrayList<T> {
+	fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> ArrayList<T> {
+		iter.into_iter().cloned().collect()
+	}
+}
+
+/// Reserves space up front based on the iterator's `size_hint`, preferring
+/// the upper bound when one is given. The upper bound is only an estimate, so
+/// it is trusted for at most `HINT_SLACK` elements beyond the lower bound;
+/// past that the list grows an extent at a time.
+impl<T> Extend<T> for ArrayList<T> {
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		let iter = iter.into_iter();
+		let (lower, upper) = iter.size_hint();
+		let cap = lower.saturating_add(HINT_SLACK);
+		self.reserve(upper.map_or(lower, |upper| upper.min(cap)));
+		for item in iter {
+			self.push(item);
+		}
+	}
+}
+
+impl<T> IntoIterator for ArrayList<T> {
+	type Item = T;
+	type IntoIter = IntoIter<T>;
+
+	fn into_iter(self) -> IntoIter<T> {
+		self.into_iter_from(0)
+	}
+}
+
+impl<T> AsRef<[T]> for ArrayList<T> {
+	fn as_ref(&self) -> &[T] {
+		self.as_slice()
+	}
+}
+
+impl<T> AsMut<[T]> for ArrayList<T> {
+	fn as_mut(&mut self) -> &mut [T] {
+		self.as_mut_slice()
+	}
+}
+
+impl<T> Borrow<[T]> for ArrayList<T> {
+	fn borrow(&self) -> &[T] {
+		self.as_slice()
+	}
+}
+
+impl<T> BorrowMut<[T]> for ArrayList<T> {
+	fn borrow_mut(&mut self) -> &mut [T] {
+		self.as_mut_slice()
+	}
+}
+
+impl io::Write for ArrayList<u8> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.extend_from_slice(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+impl<T> From<ArrayList<T>> for Box<[T]> {
+	fn from(list: ArrayList<T>) -> Box<[T]> {
+		list.into_vec().into_boxed_slice()
+	}
+}
+
+impl<T> From<ArrayList<T>> for Rc<[T]> {
+	fn from(list: ArrayList<T>) -> Rc<[T]> {
+		Rc::from(Box::from(list))
+	}
+}
+
+impl<T> From<ArrayList<T>> for Arc<[T]> {
+	fn from(list: ArrayList<T>) -> Arc<[T]> {
+		Arc::from(Box::from(list))
+	}
+}
+
+impl<T> Index<usize> for ArrayList<T> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &Self::Output {
+		if index >= self.len {
+			panic!("Index out of bounds");
+		}
+		unsafe { &(*(self.buf.add(index))) }
+	}
+}
+
+impl<T> IndexMut<usize> for ArrayList<T> {
+	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+		if index >= self.len {
+			panic!("Index out of bounds");
+		}
+		unsafe { &mut (*(self.buf.add(index))) }
+	}
+}
+
+impl<T: PartialEq> PartialEq for ArrayList<T> {
+	fn eq(&self, other: &Self) -> bool {
+		let mut equal = self.len() == other.len();
+		let mut i = 0;
+		let len = self.len();
+		while equal && i < len {
+			if self[i] != other[i] {
+				equal = false;
+			}
+			i += 1;
+		}
+		equal
+	}
+}
+
+impl<T: PartialEq, const N: usize> PartialEq<[T; N]> for ArrayList<T> {
+	fn eq(&self, other: &[T; N]) -> bool {
+		self.as_slice() == &other[..]
+	}
+}
+
+impl<T: Debug> Debug for ArrayList<T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_struct(&("ArrayList<".to_owned() + std::any::type_name::<T>() + ">"))
+			.field("len", &self.len)
+			.field("buf_extents", &self.buf_extents)
+			.finish()?;
+
+		fmt.write_str(" ")?;
+
+		let mut dbg = fmt.debug_list();
+		for i in 0..self.len() {
+			dbg.entry(&self[i]);
+		}
+		dbg.finish()
+	}
+}
+
+struct DebugVerbose<'a, T>(&'a ArrayList<T>);
+
+impl<'a, T: Debug> Debug for DebugVerbose<'a, T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let list = self.0;
+		fmt.debug_struct(&("ArrayList<".to_owned() + std::any::type_name::<T>() + ">"))
+			.field("len", &list.len)
+			.field("capacity", &list.capacity())
+			.field("extents", &list.buf_extents)
+			.field("elements", &list.as_slice())
+			.field("spare", &(list.capacity() - list.len))
+			.finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::collection::test_alloc::{allocations, deallocations};
+	use std::cell::Cell;
+
+	#[test]
+	fn i32_new() {
+		let a = ArrayList::<i32>::new();
+		assert_eq!(a.len(), 0);
+	}
+
+	#[test]
+	fn i32_with_capacity() {
+		let a = ArrayList::<i32>::with_capacity(34);
+		assert_eq!(a.len(), 0);
+		assert_eq!(a.capacity(), 48);
+	}
+
+	#[test]
+	fn i32_from_slice() {
+		let a = ArrayList::from(&[] as &[i32]);
+		assert_eq!(a, ArrayList::new());
+	}
+
+	#[test]
 	fn i32_clear() {
 		let mut a =
 			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16] as &[i32]);
-		assert_eq!(a.buf_extents, 2);
+		assert_eq!(a.buf_extents, 2);
+		a.clear();
+		assert_eq!(a, ArrayList::new());
+		assert_eq!(a.buf_extents, 0);
+	}
+
+	#[test]
+	fn i32_index() {
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a[3], 69);
+	}
+
+	#[test]
+	fn i32_index_mut() {
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		a[2] = -1;
+		assert_eq!(a, ArrayList::from(&[4, 2, -1, 69] as &[i32]));
+	}
+
+	#[test]
+	fn i32_insert() {
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.buf_extents, 1);
+		a.insert(3, 1337);
+		assert_eq!(a, ArrayList::from(&[4, 2, 0, 1337, 69] as &[i32]));
+		assert_eq!(a.buf_extents, 1);
+	}
+
+	#[test]
+	fn i32_insert_reallocate() {
+		let mut a =
+			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15] as &[i32]);
+		assert_eq!(a.buf_extents, 1);
+		a.insert(3, -99);
+		assert_eq!(
+			a,
+			ArrayList::from(&[0, 1, 2, -99, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15] as &[i32])
+		);
+		assert_eq!(a.buf_extents, 2);
+	}
+
+	#[test]
+	fn i32_push() {
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.buf_extents, 1);
+		a.push(-5);
+		assert_eq!(a, ArrayList::from(&[4, 2, 0, 69, -5] as &[i32]));
+		assert_eq!(a.buf_extents, 1);
+	}
+
+	#[test]
+	fn i32_push_realloc() {
+		let mut a =
+			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15] as &[i32]);
+		assert_eq!(a.buf_extents, 1);
+		a.push(-5);
+		assert_eq!(
+			a,
+			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, -5] as &[i32])
+		);
+		assert_eq!(a.buf_extents, 2);
+	}
+
+	#[test]
+	fn i32_remove() {
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.buf_extents, 1);
+		assert_eq!(a.remove(1), 2);
+		assert_eq!(a, ArrayList::from(&[4, 0, 69] as &[i32]));
+		assert_eq!(a.buf_extents, 1);
+	}
+
+	#[test]
+	fn i32_remove_realloc() {
+		let mut a =
+			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16] as &[i32]);
+		assert_eq!(a.buf_extents, 2);
+		assert_eq!(a.remove(4), 4);
+		assert_eq!(
+			a,
+			ArrayList::from(&[0, 1, 2, 3, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16] as &[i32])
+		);
+		assert_eq!(a.buf_extents, 1);
+	}
+
+	#[test]
+	fn i32_remove_no_auto_shrink() {
+		let mut a = ArrayList::from(&[0; 40] as &[i32]);
+		a.set_auto_shrink(false);
+		assert_eq!(a.buf_extents, 3);
+		for _ in 0..30 {
+			a.remove(0);
+		}
+		assert_eq!(a.len(), 10);
+		assert_eq!(a.buf_extents, 3);
+		a.clear();
+		assert_eq!(a.buf_extents, 3);
+		a.set_auto_shrink(true);
+		a.push(1);
+		a.remove(0);
+		assert_eq!(a.buf_extents, 0);
+	}
+
+	#[test]
+	fn i32_find_subslice() {
+		let a = ArrayList::from(&[1, 2, 3, 4, 5] as &[i32]);
+		assert_eq!(a.find_subslice(&[3, 4]), Some(2));
+		assert_eq!(a.find_subslice(&[1]), Some(0));
+		assert_eq!(a.find_subslice(&[4, 5]), Some(3));
+		assert_eq!(a.find_subslice(&[]), Some(0));
+		assert!(a.contains_subslice(&[2, 3, 4]));
+	}
+
+	#[test]
+	fn i32_find_subslice_missing() {
+		let a = ArrayList::from(&[1, 2, 3, 4, 5] as &[i32]);
+		assert_eq!(a.find_subslice(&[4, 3]), None);
+		assert_eq!(a.find_subslice(&[5, 6]), None);
+		assert_eq!(a.find_subslice(&[1, 2, 3, 4, 5, 6]), None);
+		assert!(!a.contains_subslice(&[0]));
+	}
+
+	#[test]
+	fn i32_builder_remaining_past_reservation() {
+		let mut b = ArrayListBuilder::with_capacity(2);
+		// Bypasses the debug assertion, as a release build would.
+		for i in 0..3 {
+			b.list.push(i);
+		}
+		assert_eq!(b.remaining(), 0);
+	}
+
+	#[test]
+	fn i32_builder() {
+		let head: Vec<i32> = (0..40).collect();
+		let tail: Vec<i32> = (60..100).collect();
+		let mut b = ArrayListBuilder::with_capacity(100);
+		let buf = b.list.buf;
+		assert_eq!(b.list.buf_extents, 7);
+		b.extend_from_slice(&head);
+		for i in 40..60 {
+			b.push(i);
+		}
+		b.extend_from_slice(&tail);
+		assert_eq!(b.remaining(), 0);
+		let a = b.finish();
+		assert_eq!(a.buf, buf);
+		assert_eq!(a.buf_extents, 7);
+		assert_eq!(a, ArrayList::from(&(0..100).collect::<Vec<i32>>()[..]));
+	}
+
+	#[test]
+	fn i32_as_chunks() {
+		let a = ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9] as &[i32]);
+		let (chunks, rem) = a.as_chunks::<4>();
+		assert_eq!(chunks, &[[0, 1, 2, 3], [4, 5, 6, 7]]);
+		assert_eq!(rem, &[8, 9]);
+	}
+
+	#[test]
+	fn i32_insert_front() {
+		let mut a = ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14] as &[i32]);
+		a.insert(0, -1);
+		assert_eq!(
+			a,
+			ArrayList::from(&[-1, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14] as &[i32])
+		);
+		assert_eq!(a.buf_extents, 1);
+		a.insert(0, -2);
+		assert_eq!(a[0], -2);
+		assert_eq!(a[16], 14);
+		assert_eq!(a.buf_extents, 2);
+	}
+
+	#[test]
+	fn i32_insert_back() {
+		let mut a = ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14] as &[i32]);
+		a.insert(15, 15);
+		assert_eq!(
+			a,
+			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15] as &[i32])
+		);
+		a.insert(16, 16);
+		assert_eq!(a[16], 16);
+		assert_eq!(a.buf_extents, 2);
+		let mut b = ArrayList::new();
+		b.insert(0, 1);
+		assert_eq!(b, ArrayList::from(&[1] as &[i32]));
+	}
+
+	#[test]
+	fn i32_to_vec() {
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.to_vec(), vec![4, 2, 0, 69]);
+		assert_eq!(a.len(), 4);
+	}
+
+	#[test]
+	fn i32_clone_into_vec() {
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		let mut v = Vec::with_capacity(32);
+		v.push(7);
+		a.clone_into_vec(&mut v);
+		assert_eq!(v, vec![4, 2, 0, 69]);
+		assert!(v.capacity() >= 32);
+	}
+
+	#[test]
+	fn i32_count_if() {
+		let a = ArrayList::from(&(0..10).collect::<Vec<i32>>()[..]);
+		assert_eq!(a.count_if(|x| x % 2 == 0), 5);
+		assert_eq!(a.count_if(|x| *x > 100), 0);
+	}
+
+	#[test]
+	fn i32_fold() {
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.fold(0, |acc, x| acc + x), 75);
+		assert_eq!(ArrayList::<i32>::new().fold(1, |acc, x| acc * x), 1);
+	}
+
+	#[test]
+	fn i32_for_each() {
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		let mut seen = Vec::new();
+		a.for_each(|x| seen.push(*x));
+		assert_eq!(seen, vec![4, 2, 0, 69]);
+	}
+
+	#[test]
+	fn i32_swap_with_slice() {
+		let mut a = ArrayList::from(&[1, 2, 3] as &[i32]);
+		let mut b = [7, 8, 9];
+		a.swap_with_slice(&mut b);
+		assert_eq!(a, ArrayList::from(&[7, 8, 9] as &[i32]));
+		assert_eq!(b, [1, 2, 3]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn i32_swap_with_slice_mismatch() {
+		let mut a = ArrayList::from(&[1, 2, 3] as &[i32]);
+		a.swap_with_slice(&mut [1, 2]);
+	}
+
+	#[test]
+	fn i32_push_bounded() {
+		let mut a = ArrayList::new();
+		let evicted: Vec<Option<i32>> = (1..=5).map(|i| a.push_bounded(i, 3)).collect();
+		assert_eq!(evicted, vec![None, None, None, Some(1), Some(2)]);
+		assert_eq!(a, ArrayList::from(&[3, 4, 5] as &[i32]));
+	}
+
+	#[test]
+	fn i32_push_bounded_over_full() {
+		let mut a = ArrayList::from(&[1, 2, 3, 4, 5] as &[i32]);
+		assert_eq!(a.push_bounded(6, 3), Some(3));
+		assert_eq!(a, [4, 5, 6]);
+		assert_eq!(a.push_bounded(7, 3), Some(4));
+		assert_eq!(a, [5, 6, 7]);
+		let mut b = ArrayList::from(&[1, 2] as &[i32]);
+		assert_eq!(b.push_bounded(3, 0), Some(3));
+		assert!(b.is_empty());
+	}
+
+	#[test]
+	fn i32_push_bounded_full_extent() {
+		let mut a: ArrayList<i32> = (0..16).collect();
+		let before = allocations();
+		for i in 16..1016 {
+			assert_eq!(a.push_bounded(i, 16), Some(i - 16));
+		}
+		assert_eq!(allocations() - before, 0);
+		assert_eq!(a.as_slice(), (1000..1016).collect::<Vec<i32>>().as_slice());
+	}
+
+	#[test]
+	#[should_panic(expected = "capacity overflow")]
+	fn i32_with_capacity_overflow() {
+		ArrayList::<i32>::with_capacity(usize::MAX);
+	}
+
+	#[test]
+	#[should_panic(expected = "capacity overflow")]
+	fn i32_with_capacity_too_large() {
+		ArrayList::<i32>::with_capacity(isize::MAX as usize / 2);
+	}
+
+	#[test]
+	fn i32_iter_mut() {
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		for x in a.iter_mut() {
+			*x *= 2;
+		}
+		assert_eq!(a, ArrayList::from(&[8, 4, 0, 138] as &[i32]));
+	}
+
+	#[test]
+	fn i32_iter_mut_rev() {
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		let mut n = 0;
+		for x in a.iter_mut().rev() {
+			*x += n;
+			n += 10;
+		}
+		assert_eq!(a, ArrayList::from(&[34, 22, 10, 69] as &[i32]));
+	}
+
+	#[test]
+	fn i32_iter_mut_both_ends() {
+		let mut a = ArrayList::from(&[1, 2, 3] as &[i32]);
+		let mut it = a.iter_mut();
+		let front = it.next().unwrap();
+		let back = it.next_back().unwrap();
+		let middle = it.next_back().unwrap();
+		assert!(it.next().is_none());
+		assert!(it.next_back().is_none());
+		*front = 10;
+		*back = 30;
+		*middle = 20;
+		assert_eq!(a, ArrayList::from(&[10, 20, 30] as &[i32]));
+	}
+
+	#[test]
+	fn i32_chunk_by() {
+		let a = ArrayList::from(&[1, 1, 2, 3, 3] as &[i32]);
+		let chunks: Vec<&[i32]> = a.chunk_by(|x, y| x == y).collect();
+		assert_eq!(chunks, vec![&[1, 1][..], &[2][..], &[3, 3][..]]);
+		assert_eq!(ArrayList::<i32>::new().chunk_by(|x, y| x == y).count(), 0);
+	}
+
+	#[test]
+	fn option_box_insert() {
+		let mut a: ArrayList<Option<Box<i32>>> = ArrayList::new();
+		for i in 0..20 {
+			a.push(if i % 3 == 0 { None } else { Some(Box::new(i)) });
+		}
+		a.insert(10, Some(Box::new(-1)));
+		a.insert(5, None);
+		assert_eq!(a.len(), 22);
+		assert_eq!(a[5], None);
+		assert_eq!(a[11], Some(Box::new(-1)));
+		assert_eq!(a[21], Some(Box::new(19)));
+		assert_eq!(a.remove(11), Some(Box::new(-1)));
+		a.clear();
+		assert!(a.is_empty());
+	}
+
+	#[test]
+	fn i32_copied() {
+		let a =
+			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16] as &[i32]);
+		let mut b = a.copied();
+		assert_eq!(a, b);
+		assert_eq!(b.buf_extents, 2);
+		b[0] = -1;
+		assert_eq!(a[0], 0);
+	}
+
+	#[test]
+	fn string_cloned() {
+		let a = ArrayList::from(&["a".to_owned(), "b".to_owned()] as &[String]);
+		let mut b = a.cloned();
+		assert_eq!(a, b);
+		b[1].push('c');
+		assert_eq!(a[1], "b");
+		assert_eq!(b[1], "bc");
+	}
+
+	#[test]
+	fn i32_capacity_for() {
+		assert_eq!(
+			ArrayList::<i32>::capacity_for(34),
+			ArrayList::<i32>::with_capacity(34).capacity()
+		);
+		assert_eq!(ArrayList::<i32>::capacity_for(0), 0);
+		assert_eq!(ArrayList::<i32>::capacity_for(16), 16);
+	}
+
+	#[test]
+	fn i32_with_capacity_exact() {
+		let a = ArrayList::<i32>::with_capacity_exact(34);
+		assert!(a.capacity() >= 34);
+		assert_eq!(a.capacity(), ArrayList::<i32>::capacity_for(34));
+	}
+
+	#[test]
+	fn i32_flatten() {
+		let mut a = ArrayList::new();
+		a.push(ArrayList::from(&[1, 2] as &[i32]));
+		a.push(ArrayList::new());
+		a.push(ArrayList::from(&[3] as &[i32]));
+		assert_eq!(a.flatten(), ArrayList::from(&[1, 2, 3] as &[i32]));
+	}
+
+	#[test]
+	fn string_flatten() {
+		let mut a = ArrayList::new();
+		a.push(ArrayList::from(&["a".to_owned()] as &[String]));
+		a.push(ArrayList::from(
+			&["b".to_owned(), "c".to_owned()] as &[String]
+		));
+		let b = a.flatten();
+		assert_eq!(b.len(), 3);
+		assert_eq!(b[2], "c");
+	}
+
+	#[test]
+	fn i32_run_length_encode() {
+		let a = ArrayList::from(&[1, 1, 1, 2, 3, 3] as &[i32]);
+		assert_eq!(
+			a.run_length_encode(),
+			ArrayList::from(&[(1, 3usize), (2, 1), (3, 2)] as &[(i32, usize)])
+		);
+		assert!(ArrayList::<i32>::new().run_length_encode().is_empty());
+	}
+
+	#[test]
+	fn i32_from_iter() {
+		let a: ArrayList<i32> = (0..5).collect();
+		assert_eq!(a, ArrayList::from(&[0, 1, 2, 3, 4] as &[i32]));
+	}
+
+	#[test]
+	fn i32_from_iter_upper_bound() {
+		let iter = (0..40).filter(|_| true);
+		assert_eq!(iter.size_hint(), (0, Some(40)));
+		let before = allocations();
+		let a: ArrayList<i32> = iter.collect();
+		assert_eq!(allocations() - before, 1);
+		assert_eq!(a.len(), 40);
+		assert_eq!(a.buf_extents, 3);
+	}
+
+	#[test]
+	fn i32_extend() {
+		let mut a = ArrayList::from(&[4, 2] as &[i32]);
+		let before = allocations();
+		a.extend((0..40).filter(|x| x % 2 == 0));
+		assert_eq!(allocations() - before, 1);
+		assert_eq!(a.len(), 22);
+		assert_eq!(a[2], 0);
+		assert_eq!(a[21], 38);
+	}
+
+	#[test]
+	fn i32_bytes_allocated() {
+		let a: ArrayList<i32> = (0..17).collect();
+		assert_eq!(a.extents(), 2);
+		assert_eq!(a.bytes_allocated(), 2 * EXTENT_LEN * 4);
+		assert_eq!(ArrayList::<i32>::with_capacity(0).bytes_allocated(), 0);
+	}
+
+	#[test]
+	fn i32_remove_tracked() {
+		let mut a: ArrayList<i32> = (0..18).collect();
+		assert_eq!(a.remove_tracked(0), (0, false));
+		assert_eq!(a.remove_tracked(0), (1, true));
+		assert_eq!(a.remove_tracked(0), (2, false));
+		assert_eq!(a.len(), 15);
+	}
+
+	#[test]
+	fn i32_fill_from_iter() {
+		let mut a = ArrayList::with_capacity(0);
+		a.reserve(5);
+		let buf = a.buf;
+		let mut rest = a.fill_from_iter(0..);
+		assert_eq!(a.len(), a.capacity());
+		assert_eq!(a.buf, buf);
+		assert_eq!(rest.next(), Some(a.len() as i32));
+		assert_eq!(a[4], 4);
+	}
+
+	#[test]
+	fn i32_fill_from_iter_short() {
+		let mut a = ArrayList::from(&[4, 2] as &[i32]);
+		let mut rest = a.fill_from_iter(0..3);
+		assert_eq!(rest.next(), None);
+		assert_eq!(a, ArrayList::from(&[4, 2, 0, 1, 2] as &[i32]));
+	}
+
+	#[test]
+	fn i32_concat() {
+		let lists = [
+			ArrayList::from(&[1, 2] as &[i32]),
+			ArrayList::from(&[3] as &[i32]),
+			ArrayList::from(&[4, 5, 6] as &[i32]),
+		];
+		let before = allocations();
+		let a = concat(&lists);
+		assert_eq!(allocations() - before, 1);
+		assert_eq!(a, ArrayList::from(&[1, 2, 3, 4, 5, 6] as &[i32]));
+		assert_eq!(lists[2].len(), 3);
+	}
+
+	#[test]
+	fn i32_position() {
+		let a = ArrayList::from(&[1, 2, 3, 4] as &[i32]);
+		assert_eq!(a.position(|x| x % 2 == 0), Some(1));
+		assert_eq!(a.position(|x| *x > 4), None);
+	}
+
+	#[test]
+	fn i32_rposition() {
+		let a = ArrayList::from(&[1, 2, 3, 4] as &[i32]);
+		assert_eq!(a.rposition(|x| x % 2 == 0), Some(3));
+		assert_eq!(a.rposition(|x| *x < 1), None);
+	}
+
+	#[test]
+	fn i32_as_ref() {
+		fn sum(x: impl AsRef<[i32]>) -> i32 {
+			x.as_ref().iter().sum()
+		}
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(sum(&a), 75);
+		assert_eq!(sum(a), 75);
+	}
+
+	#[test]
+	fn i32_as_mut() {
+		fn negate(mut x: impl AsMut<[i32]>) {
+			x.as_mut().iter_mut().for_each(|x| *x = -*x);
+		}
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		negate(&mut a);
+		assert_eq!(a, ArrayList::from(&[-4, -2, 0, -69] as &[i32]));
+	}
+
+	#[test]
+	fn i32_borrow() {
+		let mut a = ArrayList::from(&[4, 2] as &[i32]);
+		let s: &[i32] = a.borrow();
+		assert_eq!(s, &[4, 2]);
+		let s: &mut [i32] = a.borrow_mut();
+		s[0] = 1;
+		assert_eq!(a[0], 1);
+	}
+
+	#[test]
+	fn i32_extend_from_slice() {
+		let mut a = ArrayList::from(&[4, 2] as &[i32]);
+		a.extend_from_slice(&[0, 69]);
+		assert_eq!(a, ArrayList::from(&[4, 2, 0, 69] as &[i32]));
+	}
+
+	#[test]
+	fn u8_write() {
+		use std::io::Write;
+		let mut a = ArrayList::new();
+		a.write_all(b"hello").unwrap();
+		write!(a, " {}", 42).unwrap();
+		a.flush().unwrap();
+		assert_eq!(a.as_slice(), b"hello 42");
+	}
+
+	#[test]
+	fn u8_read_to_end() {
+		let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+		let mut a = ArrayList::from(&b"xy"[..]);
+		let mut reader = &data[..];
+		assert_eq!(a.read_to_end(&mut reader).unwrap(), 1000);
+		assert_eq!(a.len(), 1002);
+		assert_eq!(&a.as_slice()[..2], b"xy");
+		assert_eq!(&a.as_slice()[2..], &data[..]);
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn i32_from_iter_ref() {
+		let a: ArrayList<i32> = [1, 2, 3].iter().collect();
+		assert_eq!(a, ArrayList::from(&[1, 2, 3] as &[i32]));
+	}
+
+	struct DropCounter<'a>(&'a Cell<usize>);
+
+	impl<'a> PartialEq for DropCounter<'a> {
+		fn eq(&self, _: &Self) -> bool {
+			true
+		}
+	}
+
+	impl<'a> Drop for DropCounter<'a> {
+		fn drop(&mut self) {
+			self.0.set(self.0.get() + 1);
+		}
+	}
+
+	fn drop_counters(drops: &Cell<usize>, n: usize) -> ArrayList<DropCounter<'_>> {
+		(0..n).map(|_| DropCounter(drops)).collect()
+	}
+
+	struct PanicOnDrop<'a>(&'a Cell<usize>, bool);
+
+	impl<'a> Drop for PanicOnDrop<'a> {
+		fn drop(&mut self) {
+			self.0.set(self.0.get() + 1);
+			if self.1 {
+				panic!("drop panic");
+			}
+		}
+	}
+
+	#[test]
+	fn i32_into_iter() {
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.into_iter().collect::<Vec<i32>>(), vec![4, 2, 0, 69]);
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.into_iter().rev().collect::<Vec<i32>>(), vec![69, 0, 2, 4]);
+	}
+
+	#[test]
+	fn i32_into_iter_from() {
+		let a = ArrayList::from(&[1, 2, 3, 4, 5] as &[i32]);
+		let it = a.into_iter_from(2);
+		assert_eq!(it.len(), 3);
+		assert_eq!(it.collect::<Vec<i32>>(), vec![3, 4, 5]);
+	}
+
+	#[test]
+	fn drop_into_iter_from() {
+		let drops = Cell::new(0);
+		let a = drop_counters(&drops, 5);
+		let mut it = a.into_iter_from(2);
+		assert_eq!(drops.get(), 2);
+		drop(it.next());
+		assert_eq!(drops.get(), 3);
+		drop(it);
+		assert_eq!(drops.get(), 5);
+	}
+
+	#[test]
+	#[should_panic]
+	fn i32_into_iter_from_out_of_bounds() {
+		ArrayList::from(&[1, 2] as &[i32]).into_iter_from(3);
+	}
+
+	#[test]
+	fn u8_resize_zeroed() {
+		let mut a = ArrayList::from(&[1u8, 2, 3][..]);
+		a.resize_zeroed(1000);
+		assert_eq!(a.len(), 1000);
+		assert_eq!(&a.as_slice()[..3], &[1, 2, 3]);
+		assert!(a.as_slice()[3..].iter().all(|x| *x == 0));
+		a.resize_zeroed(2);
+		assert_eq!(a, ArrayList::from(&[1u8, 2][..]));
+		assert_eq!(a.buf_extents, 1);
+	}
+
+	#[test]
+	fn i32_at() {
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.at(3), Ok(&69));
+		let err = a.at(4).unwrap_err();
+		assert_eq!(err, IndexError { index: 4, len: 4 });
+		assert_eq!(err.to_string(), "Index 4 out of bounds for length 4");
+	}
+
+	#[test]
+	fn i32_iter_step() {
+		let a: ArrayList<i32> = (0..10).collect();
+		assert_eq!(
+			a.iter_step(3).copied().collect::<Vec<i32>>(),
+			vec![0, 3, 6, 9]
+		);
+		assert_eq!(a.iter_step(20).copied().collect::<Vec<i32>>(), vec![0]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn i32_iter_step_zero() {
+		let _ = ArrayList::<i32>::new().iter_step(0);
+	}
+
+	#[test]
+	fn i32_push_n() {
+		let mut a = ArrayList::from(&[-1] as &[i32]);
+		let before = allocations();
+		a.push_n(20, |i| (i * i) as i32);
+		assert_eq!(allocations() - before, 1);
+		let mut expected = vec![-1];
+		expected.extend((0..20).map(|i| i * i));
+		assert_eq!(a.to_vec(), expected);
+	}
+
+	#[test]
+	fn i32_retain() {
+		let mut a: ArrayList<i32> = (0..20).collect();
+		a.retain(|x| x % 3 == 0);
+		assert_eq!(a, ArrayList::from(&[0, 3, 6, 9, 12, 15, 18] as &[i32]));
+		assert_eq!(a.buf_extents, 1);
+	}
+
+	#[test]
+	fn i32_retain_panic() {
+		use std::panic::{self, AssertUnwindSafe};
+		let mut a = ArrayList::from(&[1, 2, 3, 4, 5] as &[i32]);
+		let mut seen = 0;
+		let result = panic::catch_unwind(AssertUnwindSafe(|| {
+			a.retain(|x| {
+				seen += 1;
+				if seen == 3 {
+					panic!("predicate panic");
+				}
+				*x != 1
+			})
+		}));
+		assert!(result.is_err());
+		assert_eq!(a, ArrayList::from(&[2, 3, 4, 5] as &[i32]));
+		a.push(6);
+		assert_eq!(a.len(), 5);
+	}
+
+	#[test]
+	fn drop_retain_panic() {
+		use std::panic::{self, AssertUnwindSafe};
+		let drops = Cell::new(0);
+		let mut a = drop_counters(&drops, 5);
+		let mut seen = 0;
+		let result = panic::catch_unwind(AssertUnwindSafe(|| {
+			a.retain(|_| {
+				seen += 1;
+				if seen == 3 {
+					panic!("predicate panic");
+				}
+				seen % 2 == 0
+			})
+		}));
+		assert!(result.is_err());
+		assert_eq!(drops.get(), 1);
+		assert_eq!(a.len(), 4);
+		drop(a);
+		assert_eq!(drops.get(), 5);
+	}
+
+	#[test]
+	fn string_min_by_key() {
+		let a: ArrayList<String> = ["apple", "fig", "banana"]
+			.iter()
+			.map(|s| s.to_string())
+			.collect();
+		assert_eq!(a.min_by_key(|s| s.len()).unwrap(), "fig");
+		assert_eq!(ArrayList::<String>::new().min_by_key(|s| s.len()), None);
+	}
+
+	#[test]
+	fn string_max_by_key() {
+		let a: ArrayList<String> = ["apple", "fig", "banana"]
+			.iter()
+			.map(|s| s.to_string())
+			.collect();
+		assert_eq!(a.max_by_key(|s| s.len()).unwrap(), "banana");
+	}
+
+	#[test]
+	fn i32_get_rel() {
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.get_rel(-1), Some(&69));
+		assert_eq!(a.get_rel(-4), Some(&4));
+		assert_eq!(a.get_rel(1), Some(&2));
+		assert_eq!(a.get_rel(4), None);
+		assert_eq!(a.get_rel(-(a.len() as isize) - 1), None);
+		assert_eq!(a.get_rel(isize::MIN), None);
+	}
+
+	#[test]
+	fn i32_reset() {
+		let mut a: ArrayList<i32> = (0..40).collect();
+		let capacity = a.capacity();
+		a.reset();
+		assert!(a.is_empty());
+		assert_eq!(a.capacity(), capacity);
+		a.push(1);
+		assert_eq!(a, ArrayList::from(&[1] as &[i32]));
+	}
+
+	#[test]
+	fn drop_recycle() {
+		let drops = Cell::new(0);
+		let a = drop_counters(&drops, 20);
+		let a = a.recycle();
+		assert_eq!(drops.get(), 20);
+		assert!(a.is_empty());
+		assert_eq!(a.capacity(), 32);
+	}
+
+	#[test]
+	fn i32_merge_sorted() {
+		let mut a = ArrayList::from(&[1, 3, 5] as &[i32]);
+		a.merge_sorted(&[2, 4, 6]);
+		assert_eq!(a, ArrayList::from(&[1, 2, 3, 4, 5, 6] as &[i32]));
+		a.merge_sorted(&[0, 0, 7]);
+		assert_eq!(a, ArrayList::from(&[0, 0, 1, 2, 3, 4, 5, 6, 7] as &[i32]));
+		let mut b = ArrayList::new();
+		b.merge_sorted(&[1, 2]);
+		assert_eq!(b, ArrayList::from(&[1, 2] as &[i32]));
+	}
+
+	#[test]
+	fn string_merge_sorted() {
+		let mut a: ArrayList<String> = ["b", "d"].iter().map(|s| s.to_string()).collect();
+		a.merge_sorted(&["a".to_owned(), "c".to_owned(), "e".to_owned()]);
+		assert_eq!(a.to_vec(), vec!["a", "b", "c", "d", "e"]);
+	}
+
+	#[test]
+	fn i32_merge_sorted_allocations() {
+		let mut a: ArrayList<i32> = (0..20).map(|x| x * 2).collect();
+		let other: Vec<i32> = (0..20).map(|x| x * 2 + 1).collect();
+		let before = allocations();
+		a.merge_sorted(&other);
+		assert_eq!(allocations() - before, 1);
+		assert_eq!(a.as_slice(), (0..40).collect::<Vec<i32>>().as_slice());
+	}
+
+	#[test]
+	fn string_merge_sorted_clone_panic() {
+		use std::panic::{self, AssertUnwindSafe};
+
+		#[derive(PartialEq, Eq, PartialOrd, Ord)]
+		struct Fragile(String);
+
+		impl Clone for Fragile {
+			fn clone(&self) -> Self {
+				if self.0 == "c" {
+					panic!("clone panic");
+				}
+				Fragile(self.0.clone())
+			}
+		}
+
+		let mut a: ArrayList<Fragile> = ["b", "d"].iter().map(|s| Fragile(s.to_string())).collect();
+		let other: Vec<Fragile> = ["a", "c", "e"]
+			.iter()
+			.map(|s| Fragile(s.to_string()))
+			.collect();
+		let result = panic::catch_unwind(AssertUnwindSafe(|| a.merge_sorted(&other)));
+		assert!(result.is_err());
+		let names: Vec<&str> = a.as_slice().iter().map(|f| f.0.as_str()).collect();
+		assert_eq!(names, ["b", "d", "e"]);
+	}
+
+	#[test]
+	fn i32_windows_mut() {
+		let mut a = ArrayList::from(&[0, 3, 6, 9, 12] as &[i32]);
+		let mut calls = 0;
+		a.windows_mut(3, |w| {
+			w[1] = (w[0] + w[1] + w[2]) / 3;
+			calls += 1;
+		});
+		assert_eq!(calls, 3);
+		assert_eq!(a, ArrayList::from(&[0, 3, 6, 9, 12] as &[i32]));
+		let mut b = ArrayList::from(&[0, 9, 0, 9] as &[i32]);
+		b.windows_mut(3, |w| w[1] = (w[0] + w[1] + w[2]) / 3);
+		assert_eq!(b, ArrayList::from(&[0, 3, 4, 9] as &[i32]));
+		b.windows_mut(5, |_| panic!("window larger than list"));
+	}
+
+	#[test]
+	fn i32_eq_array() {
+		let a = ArrayList::from(&[1, 2, 3] as &[i32]);
+		assert_eq!(a, [1, 2, 3]);
+		assert_ne!(a, [1, 2, 4]);
+		assert_ne!(a, [1, 2]);
+		assert_eq!(ArrayList::<i32>::new(), []);
+	}
+
+	#[test]
+	fn drop_drop_range() {
+		let drops = Cell::new(0);
+		let mut a = drop_counters(&drops, 6);
+		unsafe {
+			a.drop_range(4..6);
+			a.set_len(4);
+		}
+		assert_eq!(drops.get(), 2);
+		assert_eq!(a.len(), 4);
+		unsafe {
+			a.drop_range(0..2);
+			let buf = a.as_mut_slice().as_mut_ptr();
+			ptr::write(buf, DropCounter(&drops));
+			ptr::write(buf.add(1), DropCounter(&drops));
+		}
+		assert_eq!(drops.get(), 4);
+		drop(a);
+		assert_eq!(drops.get(), 8);
+	}
+
+	#[test]
+	fn i32_is_sorted() {
+		assert!(ArrayList::from(&[1, 2, 2, 5] as &[i32]).is_sorted());
+		assert!(!ArrayList::from(&[1, 3, 2] as &[i32]).is_sorted());
+		assert!(ArrayList::<i32>::new().is_sorted());
+		assert!(ArrayList::from(&[7] as &[i32]).is_sorted());
+	}
+
+	#[test]
+	fn i32_is_sorted_by() {
+		let a = ArrayList::from(&[5, 3, 1] as &[i32]);
+		assert!(a.is_sorted_by(|a, b| a >= b));
+		assert!(!a.is_sorted_by(|a, b| a <= b));
+		assert!(ArrayList::<i32>::new().is_sorted_by(|_, _| false));
+		assert!(ArrayList::from(&[7] as &[i32]).is_sorted_by(|_, _| false));
+	}
+
+	#[test]
+	fn i32_take_while_collect() {
+		let a = ArrayList::from(&[1, 2, 3, 10, 4] as &[i32]);
+		assert_eq!(a.take_while_collect(|x| *x < 5), [1, 2, 3]);
+	}
+
+	#[test]
+	fn i32_skip_while_collect() {
+		let a = ArrayList::from(&[1, 2, 3, 10, 4] as &[i32]);
+		assert_eq!(a.skip_while_collect(|x| *x < 5), [10, 4]);
+	}
+
+	#[test]
+	fn i32_first_last() {
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.first(), Some(&4));
+		assert_eq!(a.last(), Some(&69));
+		*a.first_mut().unwrap() = 1;
+		*a.last_mut().unwrap() = 2;
+		assert_eq!(a, [1, 2, 0, 2]);
+		assert_eq!(ArrayList::<i32>::new().first(), None);
+		assert_eq!(ArrayList::<i32>::new().last_mut(), None);
+	}
+
+	#[test]
+	fn i32_swap_ends() {
+		let mut a = ArrayList::from(&[1, 2, 3, 4] as &[i32]);
+		a.swap_ends();
+		assert_eq!(a, [4, 2, 3, 1]);
+		let mut b = ArrayList::from(&[1] as &[i32]);
+		b.swap_ends();
+		assert_eq!(b, [1]);
+		ArrayList::<i32>::new().swap_ends();
+	}
+
+	#[test]
+	fn i32_shrink_to_fit() {
+		let mut a = ArrayList::with_capacity(100);
+		a.push(1);
+		a.shrink_to_fit();
+		assert_eq!(a.buf_extents, 1);
 		a.clear();
-		assert_eq!(a, ArrayList::new());
+		a.shrink_to_fit();
 		assert_eq!(a.buf_extents, 0);
 	}
 
 	#[test]
-	fn i32_index() {
-		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
-		assert_eq!(a[3], 69);
+	fn i32_from_iter_loose_hint() {
+		let iter = (0..1000).filter(|x| *x < 100);
+		assert_eq!(iter.size_hint(), (0, Some(1000)));
+		let a: ArrayList<i32> = iter.collect();
+		assert_eq!(a.len(), 100);
+		assert!(a.capacity() - a.len() < EXTENT_LEN);
+	}
+
+	#[test]
+	fn i32_from_iter_shrinks_capped_hint() {
+		let iter = (0..1000).filter(|x| *x < 20);
+		assert_eq!(iter.size_hint(), (0, Some(1000)));
+		let a: ArrayList<i32> = iter.collect();
+		assert_eq!(a.len(), 20);
+		assert_eq!(a.extents(), extents_for(20));
+	}
+
+	#[test]
+	fn i32_from_iter_ref_loose_hint() {
+		let a: ArrayList<i32> = (0..1_000_000).collect();
+		let before = allocations();
+		// The million-element upper bound is capped, so each collect reserves
+		// HINT_SLACK slots and then shrinks to fit.
+		let b: ArrayList<i32> = a.as_slice().iter().take_while(|x| **x < 0).collect();
+		assert_eq!(allocations() - before, 1);
+		assert!(b.is_empty());
+		assert_eq!(b.extents(), 0);
+		let c: ArrayList<i32> = a.as_slice().iter().take_while(|x| **x < 20).collect();
+		assert_eq!(allocations() - before, 3);
+		assert_eq!(c.extents(), 2);
+	}
+
+	#[test]
+	fn aligned_push_realloc() {
+		#[repr(align(128))]
+		#[derive(Debug, PartialEq)]
+		struct Aligned(u32);
+
+		assert_eq!(ArrayList::<Aligned>::layout().size(), 128 * EXTENT_LEN);
+		let mut a = ArrayList::new();
+		for i in 0..40 {
+			a.push(Aligned(i));
+		}
+		assert_eq!(a.buf_extents, 3);
+		for i in 0..40 {
+			assert_eq!(a[i], Aligned(i as u32));
+			assert_eq!(&a[i] as *const Aligned as usize % 128, 0);
+		}
+		for _ in 0..30 {
+			a.remove(0);
+		}
+		assert_eq!(a.buf_extents, 1);
+		assert_eq!(a.first(), Some(&Aligned(30)));
+		assert_eq!(a.last(), Some(&Aligned(39)));
+	}
+
+	#[test]
+	fn i32_sort() {
+		let mut a = ArrayList::from(&[3, 1, 2, 1] as &[i32]);
+		a.sort();
+		assert_eq!(a, [1, 1, 2, 3]);
+	}
+
+	#[test]
+	fn i32_drain_sorted() {
+		let mut a = ArrayList::from(&[3, 1, 2] as &[i32]);
+		assert_eq!(a.drain_sorted().collect::<Vec<i32>>(), vec![1, 2, 3]);
+		assert!(a.is_empty());
+		a.push(5);
+		assert_eq!(a, [5]);
+		a.set_auto_shrink(false);
+		let _ = a.drain_sorted();
+		assert!(!a.auto_shrink);
+	}
+
+	#[test]
+	fn i32_dedup() {
+		let mut a = ArrayList::from(&[1, 1, 2, 3, 3, 3, 1] as &[i32]);
+		a.dedup();
+		assert_eq!(a, [1, 2, 3, 1]);
+		let mut b = ArrayList::from(&[7] as &[i32]);
+		b.dedup();
+		assert_eq!(b, [7]);
+	}
+
+	#[test]
+	fn i32_sort_dedup() {
+		let mut a = ArrayList::from(&[3, 1, 2, 3, 1] as &[i32]);
+		a.sort_dedup();
+		assert_eq!(a, [1, 2, 3]);
+	}
+
+	#[test]
+	fn i32_chunks_mut() {
+		let mut a: ArrayList<i32> = (0..10).collect();
+		let mut sizes = Vec::new();
+		for chunk in a.chunks_mut(4) {
+			sizes.push(chunk.len());
+			for x in chunk.iter_mut() {
+				*x *= 2;
+			}
+		}
+		assert_eq!(sizes, vec![4, 4, 2]);
+		assert_eq!(a, [0, 2, 4, 6, 8, 10, 12, 14, 16, 18]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn i32_chunks_mut_zero() {
+		ArrayList::<i32>::new().chunks_mut(0);
+	}
+
+	#[test]
+	fn i32_pop() {
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.pop(), Some(69));
+		assert_eq!(a, [4, 2, 0]);
+		a.push(1);
+		assert_eq!(a, [4, 2, 0, 1]);
+		assert_eq!(ArrayList::<i32>::new().pop(), None);
+	}
+
+	#[test]
+	fn i32_swap_remove() {
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert_eq!(a.swap_remove(1), 2);
+		assert_eq!(a, [4, 69, 0]);
+		assert_eq!(a.swap_remove(2), 0);
+		assert_eq!(a, [4, 69]);
+		a.push(5);
+		assert_eq!(a, [4, 69, 5]);
+	}
+
+	#[test]
+	fn string_remove_reuse_slot() {
+		let mut a: ArrayList<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+		assert_eq!(a.remove(0), "a");
+		assert_eq!(a.pop().unwrap(), "c");
+		assert_eq!(a.swap_remove(0), "b");
+		a.push("d".to_owned());
+		a.push("e".to_owned());
+		assert_eq!(a.to_vec(), vec!["d", "e"]);
+	}
+
+	#[test]
+	fn i32_reserve_extents() {
+		let mut a = ArrayList::<i32>::with_capacity(0);
+		a.reserve_extents(5);
+		assert_eq!(a.extents(), 5);
+		assert_eq!(a.capacity(), 5 * EXTENT_LEN);
+		a.reserve_extents(2);
+		assert_eq!(a.extents(), 5);
 	}
 
 	#[test]
-	fn i32_index_mut() {
+	fn i32_into_boxed_slice() {
+		let b: Box<[i32]> = ArrayList::from(&[4, 2, 0, 69] as &[i32]).into();
+		assert_eq!(&b[..], &[4, 2, 0, 69]);
+	}
+
+	#[test]
+	fn string_into_rc() {
+		let a: ArrayList<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+		let rc: Rc<[String]> = a.into();
+		let other = Rc::clone(&rc);
+		assert_eq!(Rc::strong_count(&rc), 2);
+		assert_eq!(&other[..], &["a".to_owned(), "b".to_owned()]);
+	}
+
+	#[test]
+	fn i32_into_arc() {
+		let arc: Arc<[i32]> = ArrayList::from(&[4, 2, 0, 69] as &[i32]).into();
+		let other = Arc::clone(&arc);
+		assert!(Arc::ptr_eq(&arc, &other));
+		assert_eq!(&other[..], &[4, 2, 0, 69]);
+	}
+
+	#[test]
+	fn i32_append_bounded() {
+		let mut a = ArrayList::new();
+		let mut rest = a.append_bounded(0.., 5);
+		assert_eq!(a, [0, 1, 2, 3, 4]);
+		assert_eq!(rest.next(), Some(5));
+		let mut rest = a.append_bounded(10..12, 10);
+		assert_eq!(rest.next(), None);
+		assert_eq!(a, [0, 1, 2, 3, 4, 10, 11]);
+		let mut rest = a.append_bounded(20..30, 3);
+		assert_eq!(rest.next(), Some(20));
+		assert_eq!(a.len(), 7);
+	}
+
+	#[test]
+	fn i32_swap_remove_front() {
 		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
-		a[2] = -1;
-		assert_eq!(a, ArrayList::from(&[4, 2, -1, 69] as &[i32]));
+		assert_eq!(a.swap_remove_front(), Some(4));
+		assert_eq!(a, [69, 2, 0]);
+		let mut b = ArrayList::from(&[1] as &[i32]);
+		assert_eq!(b.swap_remove_front(), Some(1));
+		assert_eq!(b.swap_remove_front(), None);
 	}
 
 	#[test]
-	fn i32_insert() {
+	fn unit_capacity_saturates() {
+		let a = ArrayList::<()>::with_capacity(usize::MAX);
+		assert_eq!(a.extents(), usize::MAX / EXTENT_LEN + 1);
+		assert_eq!(a.capacity(), usize::MAX);
+		assert_eq!(ArrayList::<()>::capacity_for(usize::MAX), usize::MAX);
+		assert_eq!(extents_for(usize::MAX), usize::MAX / EXTENT_LEN + 1);
+	}
+
+	#[test]
+	fn unit_capacity_near_max() {
+		let mut a = ArrayList::<()>::with_capacity(usize::MAX - 20);
+		assert_eq!(a.capacity(), usize::MAX - (EXTENT_LEN - 1));
+		assert!(a.capacity() >= usize::MAX - 20);
+		a.push(());
+		unsafe {
+			a.set_len(usize::MAX - 20);
+		}
+		a.push(());
+		assert_eq!(a.len(), usize::MAX - 19);
+		assert_eq!(a.extents(), usize::MAX / EXTENT_LEN);
+		assert_eq!(a.bytes_allocated(), 0);
+	}
+
+	#[test]
+	fn i32_sum() {
+		let a = ArrayList::from(&[1, 2, 3] as &[i32]);
+		assert_eq!(a.sum::<i32>(), 6);
+		assert_eq!(ArrayList::<i32>::new().sum::<i32>(), 0);
+	}
+
+	#[test]
+	fn i32_product() {
+		let a = ArrayList::from(&[1, 2, 3, 4] as &[i32]);
+		assert_eq!(a.product::<i32>(), 24);
+		assert_eq!(ArrayList::<i32>::new().product::<i32>(), 1);
+	}
+
+	#[derive(Debug, Clone, Copy)]
+	struct Keyed(i32, &'static str);
+
+	impl PartialEq for Keyed {
+		fn eq(&self, other: &Self) -> bool {
+			self.0 == other.0
+		}
+	}
+
+	#[test]
+	fn keyed_dedup_keep_last() {
+		let mut a = ArrayList::from(&[Keyed(1, "a"), Keyed(1, "b"), Keyed(2, "c")] as &[Keyed]);
+		a.dedup_keep_last();
+		assert_eq!(a.len(), 2);
+		assert_eq!((a[0].0, a[0].1), (1, "b"));
+		assert_eq!((a[1].0, a[1].1), (2, "c"));
+		let mut b = ArrayList::from(&[Keyed(1, "a"), Keyed(1, "b"), Keyed(2, "c")] as &[Keyed]);
+		b.dedup();
+		assert_eq!((b[0].0, b[0].1), (1, "a"));
+	}
+
+	#[test]
+	fn drop_dedup_keep_last() {
+		let drops = Cell::new(0);
+		let mut a = drop_counters(&drops, 4);
+		a.dedup_keep_last();
+		assert_eq!(a.len(), 1);
+		assert_eq!(drops.get(), 3);
+		drop(a);
+		assert_eq!(drops.get(), 4);
+	}
+
+	#[test]
+	fn i32_from_exact() {
+		let before = allocations();
+		let a = ArrayList::from_exact(0..50);
+		assert_eq!(allocations() - before, 1);
+		assert_eq!(a.len(), 50);
+		assert_eq!(a.extents(), 4);
+		assert_eq!(a[49], 49);
+	}
+
+	#[test]
+	fn u8_make_ascii_uppercase() {
+		let mut a = ArrayList::from(&b"abc123"[..]);
+		a.make_ascii_uppercase();
+		assert_eq!(a.as_slice(), b"ABC123");
+	}
+
+	#[test]
+	fn u8_make_ascii_lowercase() {
+		let mut a = ArrayList::from(&b"ABC-xyz"[..]);
+		a.make_ascii_lowercase();
+		assert_eq!(a.as_slice(), b"abc-xyz");
+	}
+
+	#[test]
+	fn i32_cursor_peek() {
+		let a = ArrayList::from(&[4, 2, 0] as &[i32]);
+		let c = a.cursor();
+		assert_eq!(c.peek(), Some(&4));
+		assert_eq!(c.peek_next(), Some(&2));
+		assert_eq!(c.peek(), Some(&4));
+		assert_eq!(c.position(), 0);
+	}
+
+	#[test]
+	fn i32_cursor_advance() {
+		let a = ArrayList::from(&[4, 2, 0] as &[i32]);
+		let mut c = a.cursor();
+		assert_eq!(c.advance(), Some(&4));
+		assert_eq!(c.advance(), Some(&2));
+		assert_eq!(c.peek_next(), None);
+		assert_eq!(c.advance(), Some(&0));
+		assert_eq!(c.peek(), None);
+		assert_eq!(c.advance(), None);
+		assert_eq!(c.position(), 3);
+		assert_eq!(c.retreat(), Some(&0));
+		assert_eq!(c.retreat(), Some(&2));
+		assert_eq!(c.retreat(), Some(&4));
+		assert_eq!(c.retreat(), None);
+	}
+
+	#[test]
+	fn i32_binary_search() {
+		let a = ArrayList::from(&[1, 3, 5, 7] as &[i32]);
+		assert_eq!(a.binary_search(&5), Ok(2));
+		assert_eq!(a.binary_search(&4), Err(2));
+	}
+
+	#[test]
+	fn pair_binary_search_by_key() {
+		let a = ArrayList::from(&[(1u32, "a"), (4, "b"), (9, "c")][..]);
+		assert_eq!(a.binary_search_by_key(&4, |&(k, _)| k), Ok(1));
+		assert_eq!(a.binary_search_by_key(&5, |&(k, _)| k), Err(2));
+	}
+
+	#[test]
+	fn i32_into_chunks() {
+		let a: ArrayList<i32> = (0..10).collect();
+		let chunks: Vec<ArrayList<i32>> = a.into_chunks(4).collect();
+		assert_eq!(chunks.len(), 3);
+		assert_eq!(chunks[0], [0, 1, 2, 3]);
+		assert_eq!(chunks[1], [4, 5, 6, 7]);
+		assert_eq!(chunks[2], [8, 9]);
+	}
+
+	#[test]
+	fn drop_into_chunks() {
+		let drops = Cell::new(0);
+		let a = drop_counters(&drops, 10);
+		let mut chunks = a.into_chunks(4);
+		assert_eq!(chunks.next().unwrap().len(), 4);
+		assert_eq!(drops.get(), 4);
+		drop(chunks);
+		assert_eq!(drops.get(), 10);
+	}
+
+	#[test]
+	fn i32_get_many_mut() {
 		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
-		assert_eq!(a.buf_extents, 1);
-		a.insert(3, 1337);
-		assert_eq!(a, ArrayList::from(&[4, 2, 0, 1337, 69] as &[i32]));
-		assert_eq!(a.buf_extents, 1);
+		let [x, y] = a.get_many_mut([0, 3]).unwrap();
+		std::mem::swap(x, y);
+		*x += 1;
+		assert_eq!(a, [70, 2, 0, 4]);
+		assert!(a.get_many_mut([1, 4]).is_none());
 	}
 
 	#[test]
-	fn i32_insert_reallocate() {
-		let mut a =
-			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15] as &[i32]);
-		assert_eq!(a.buf_extents, 1);
-		a.insert(3, -99);
+	fn i32_get_many_mut_overlap() {
+		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert!(a.get_many_mut([1, 1]).is_none());
+		assert!(a.get_many_mut([0, 2, 0]).is_none());
+	}
+
+	#[test]
+	fn i32_push_tracked() {
+		let mut a = ArrayList::new();
+		assert!(a.push_tracked(0));
+		for i in 1..16 {
+			assert!(!a.push_tracked(i));
+		}
+		assert!(a.push_tracked(16));
+		assert!(!a.push_tracked(17));
+		assert_eq!(a.len(), 18);
+	}
+
+	#[test]
+	fn i32_contains_all() {
+		let a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
+		assert!(a.contains_all(&[69, 4, 4]));
+		assert!(!a.contains_all(&[2, 3]));
+		assert!(a.contains_all(&[]));
+	}
+
+	#[test]
+	fn u8_from_copy_slice() {
+		let data: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+		let before = allocations();
+		let a = ArrayList::from_copy_slice(&data);
+		assert_eq!(allocations() - before, 1);
+		assert_eq!(a, ArrayList::from(&data[..]));
+		assert_eq!(a.as_slice(), &data[..]);
+		assert!(ArrayList::<u8>::from_copy_slice(&[]).is_empty());
+	}
+
+	#[test]
+	fn i32_into_vec() {
+		let a: ArrayList<i32> = (0..20).collect();
+		let buf = a.buf;
+		let before = allocations();
+		let v = a.into_vec();
+		assert_eq!(allocations() - before, 0);
+		assert_eq!(v.as_ptr(), buf as *const i32);
+		assert_eq!(v.capacity(), 32);
+		assert_eq!(v, (0..20).collect::<Vec<i32>>());
 		assert_eq!(
-			a,
-			ArrayList::from(&[0, 1, 2, -99, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15] as &[i32])
+			ArrayList::from(&v[..]),
+			ArrayList::from(&(0..20).collect::<Vec<i32>>()[..])
 		);
-		assert_eq!(a.buf_extents, 2);
+		assert!(ArrayList::<i32>::with_capacity(0).into_vec().is_empty());
 	}
 
 	#[test]
-	fn i32_push() {
+	fn drop_into_vec() {
+		let drops = Cell::new(0);
+		let a = drop_counters(&drops, 20);
+		let mut v = a.into_vec();
+		assert_eq!(drops.get(), 0);
+		v.truncate(5);
+		assert_eq!(drops.get(), 15);
+		v.push(DropCounter(&drops));
+		drop(v);
+		assert_eq!(drops.get(), 21);
+	}
+
+	#[test]
+	fn unit_into_vec() {
+		let mut a = ArrayList::new();
+		a.push(());
+		a.push(());
+		assert_eq!(a.into_vec(), vec![(), ()]);
+	}
+
+	#[test]
+	fn i32_seq_sink() {
+		fn fill(sink: &mut dyn SeqSink<i32>) {
+			for i in 0..3 {
+				sink.push(i);
+			}
+		}
+		let mut a = ArrayList::from(&[4] as &[i32]);
+		fill(&mut a);
+		assert_eq!(a, [4, 0, 1, 2]);
+		let sink: &dyn SeqSink<i32> = &a;
+		assert_eq!(sink.len(), 4);
+		assert!(!sink.is_empty());
+	}
+
+	#[test]
+	fn pair_dedup_by() {
+		let mut a = ArrayList::from(&[(1u32, 2), (1, 3), (2, 1), (2, 4), (1, 1)][..]);
+		a.dedup_by(|item, kept| {
+			if item.0 == kept.0 {
+				kept.1 += item.1;
+				true
+			} else {
+				false
+			}
+		});
+		assert_eq!(a, [(1, 5), (2, 5), (1, 1)]);
+	}
+
+	#[test]
+	fn i32_iter_mut_indexed() {
 		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
-		assert_eq!(a.buf_extents, 1);
-		a.push(-5);
-		assert_eq!(a, ArrayList::from(&[4, 2, 0, 69, -5] as &[i32]));
-		assert_eq!(a.buf_extents, 1);
+		for (i, x) in a.iter_mut_indexed() {
+			*x += i as i32;
+		}
+		assert_eq!(a, [4, 3, 2, 72]);
 	}
 
 	#[test]
-	fn i32_push_realloc() {
-		let mut a =
-			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15] as &[i32]);
-		assert_eq!(a.buf_extents, 1);
-		a.push(-5);
+	fn i32_try_reserve_exact() {
+		let mut a = ArrayList::from(&[4, 2] as &[i32]);
+		assert_eq!(a.try_reserve_exact(20), Ok(()));
+		assert_eq!(a.capacity(), ArrayList::<i32>::capacity_for(22));
+		assert_eq!(a.capacity(), 32);
+		assert_eq!(a.try_reserve_exact(1), Ok(()));
+		assert_eq!(a.capacity(), 32);
+		assert_eq!(a, [4, 2]);
+	}
+
+	#[test]
+	fn i32_try_reserve_exact_overflow() {
+		let mut a = ArrayList::from(&[4, 2] as &[i32]);
 		assert_eq!(
-			a,
-			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, -5] as &[i32])
+			a.try_reserve_exact(usize::MAX),
+			Err(TryReserveError::CapacityOverflow)
 		);
-		assert_eq!(a.buf_extents, 2);
+		assert_eq!(
+			a.try_reserve_exact(usize::MAX / 2),
+			Err(TryReserveError::CapacityOverflow)
+		);
+		assert_eq!(a.capacity(), 16);
+		assert_eq!(a, [4, 2]);
 	}
 
 	#[test]
-	fn i32_remove() {
+	fn i32_map_in_place() {
 		let mut a = ArrayList::from(&[4, 2, 0, 69] as &[i32]);
-		assert_eq!(a.buf_extents, 1);
-		assert_eq!(a.remove(1), 2);
-		assert_eq!(a, ArrayList::from(&[4, 0, 69] as &[i32]));
-		assert_eq!(a.buf_extents, 1);
+		let buf = a.buf;
+		a.map_in_place(|x| x * 2);
+		assert_eq!(a, [8, 4, 0, 138]);
+		assert_eq!(a.buf, buf);
 	}
 
 	#[test]
-	fn i32_remove_realloc() {
-		let mut a =
-			ArrayList::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16] as &[i32]);
-		assert_eq!(a.buf_extents, 2);
-		assert_eq!(a.remove(4), 4);
+	fn drop_map_in_place() {
+		let drops = Cell::new(0);
+		let mut a = drop_counters(&drops, 5);
+		a.map_in_place(|old| DropCounter(old.0));
+		assert_eq!(drops.get(), 5);
+		assert_eq!(a.len(), 5);
+		drop(a);
+		assert_eq!(drops.get(), 10);
+	}
+
+	#[test]
+	fn drop_map_in_place_panic() {
+		use std::panic::{self, AssertUnwindSafe};
+		let drops = Cell::new(0);
+		let mut a = drop_counters(&drops, 5);
+		let mut calls = 0;
+		let result = panic::catch_unwind(AssertUnwindSafe(|| {
+			a.map_in_place(|old| {
+				calls += 1;
+				if calls == 3 {
+					panic!("map panic");
+				}
+				old
+			})
+		}));
+		assert!(result.is_err());
+		assert_eq!(drops.get(), 3);
+		assert_eq!(a.len(), 2);
+		drop(a);
+		assert_eq!(drops.get(), 5);
+	}
+
+	#[test]
+	fn i32_new_lazy() {
+		let before = allocations();
+		let a = ArrayList::<i32>::new();
+		assert_eq!(allocations() - before, 0);
+		assert_eq!(a.capacity(), 0);
 		assert_eq!(
-			a,
-			ArrayList::from(&[0, 1, 2, 3, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16] as &[i32])
+			ArrayList::<i32>::new().capacity(),
+			ArrayList::<i32>::with_capacity(0).capacity()
 		);
-		assert_eq!(a.buf_extents, 1);
+		assert_eq!(ArrayList::<i32>::default().capacity(), 0);
+	}
+
+	#[test]
+	fn string_sort_by_cached_key() {
+		let mut a: ArrayList<String> = ["ccc", "a", "dddd", "bb"]
+			.iter()
+			.map(|s| s.to_string())
+			.collect();
+		let mut calls = 0;
+		a.sort_by_cached_key(|s| {
+			calls += 1;
+			s.len()
+		});
+		assert_eq!(calls, a.len());
+		assert_eq!(a.to_vec(), vec!["a", "bb", "ccc", "dddd"]);
+	}
+
+	#[test]
+	fn i32_reserve_for() {
+		let mut a = ArrayList::new();
+		let before = allocations();
+		a.reserve_for(20, |a| {
+			for i in 0..20 {
+				a.push(i);
+			}
+		});
+		assert_eq!(allocations() - before, 1);
+		assert_eq!(a.len(), 20);
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "Reallocation inside reserve_for region")]
+	fn i32_reserve_for_under_reserved() {
+		let mut a = ArrayList::new();
+		a.reserve_for(1, |a| {
+			for i in 0..20 {
+				a.push(i);
+			}
+		});
+	}
+
+	#[test]
+	fn string_into_array() {
+		let a: ArrayList<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+		let arr: [String; 3] = a.into_array().unwrap();
+		assert_eq!(arr, ["a", "b", "c"]);
+	}
+
+	#[test]
+	fn i32_into_array_mismatch() {
+		let a = ArrayList::from(&[4, 2, 0] as &[i32]);
+		let a = a.into_array::<4>().unwrap_err();
+		assert_eq!(a, [4, 2, 0]);
+		assert_eq!(a.into_array::<3>(), Ok([4, 2, 0]));
+	}
+
+	#[test]
+	fn i32_argmax_argmin() {
+		let a = ArrayList::from(&[3, 7, 7, 2] as &[i32]);
+		assert_eq!(a.argmax(), Some(1));
+		assert_eq!(a.argmin(), Some(3));
+		let b: ArrayList<i32> = ArrayList::new();
+		assert_eq!(b.argmax(), None);
+		assert_eq!(b.argmin(), None);
+	}
+
+	#[test]
+	fn i32_debug_verbose() {
+		let a = ArrayList::from(&[1, 2, 3] as &[i32]);
+		let s = format!("{:?}", a.debug_verbose());
+		assert!(s.contains("len: 3"));
+		assert!(s.contains("capacity: 16"));
+		assert!(s.contains("extents: 1"));
+		assert!(s.contains("[1, 2, 3]"));
+		assert!(s.contains("spare: 13"));
+	}
+
+	#[test]
+	fn u8_hex_round_trip() {
+		for bytes in [&[][..], &[0x00][..], &[0xde, 0xad, 0xbe, 0xef][..]] {
+			let a = ArrayList::from(bytes);
+			let hex = a.to_hex();
+			assert_eq!(ArrayList::from_hex(&hex).unwrap(), a);
+		}
+		assert_eq!(ArrayList::from(&[0x0f, 0xa0][..]).to_hex(), "0fa0");
+		assert_eq!(ArrayList::from_hex("0FA0").unwrap(), [0x0f, 0xa0]);
+		assert_eq!(
+			ArrayList::from_hex("abc"),
+			Err(DecodeError::InvalidLength { len: 3 })
+		);
+		assert_eq!(
+			ArrayList::from_hex("0g"),
+			Err(DecodeError::InvalidByte {
+				index: 1,
+				byte: b'g'
+			})
+		);
+	}
+
+	#[test]
+	fn u8_base64_round_trip() {
+		let cases: [(&[u8], &str); 5] = [
+			(b"", ""),
+			(b"f", "Zg=="),
+			(b"fo", "Zm8="),
+			(b"foo", "Zm9v"),
+			(b"foobar", "Zm9vYmFy"),
+		];
+		for &(bytes, encoded) in &cases {
+			let a = ArrayList::from(bytes);
+			assert_eq!(a.to_base64(), encoded);
+			assert_eq!(ArrayList::from_base64(encoded).unwrap(), a);
+		}
+		let all: ArrayList<u8> = (0..=255).collect();
+		assert_eq!(ArrayList::from_base64(&all.to_base64()).unwrap(), all);
+		assert_eq!(
+			ArrayList::from_base64("Zg="),
+			Err(DecodeError::InvalidLength { len: 3 })
+		);
+		assert_eq!(
+			ArrayList::from_base64("Zg==Zm8="),
+			Err(DecodeError::InvalidByte {
+				index: 2,
+				byte: b'='
+			})
+		);
+		assert_eq!(
+			ArrayList::from_base64("Zm!v"),
+			Err(DecodeError::InvalidByte {
+				index: 2,
+				byte: b'!'
+			})
+		);
+	}
+
+	#[test]
+	fn i32_insert_sorted() {
+		let mut a = ArrayList::new();
+		for &x in &[3, 1, 2, 1] {
+			a.insert_sorted(x);
+		}
+		assert_eq!(a, [1, 1, 2, 3]);
+		assert_eq!(a.insert_sorted(2), 3);
+	}
+
+	#[test]
+	fn i32_interleave() {
+		let a = ArrayList::from(&[1, 2, 3] as &[i32]);
+		assert_eq!(a.interleave(&[10, 20]), [1, 10, 2, 20, 3]);
+		assert_eq!(a.interleave(&[10, 20, 30, 40]), [1, 10, 2, 20, 3, 30, 40]);
+		assert_eq!(a.interleave(&[]), [1, 2, 3]);
+	}
+
+	#[test]
+	fn drop_drain_each() {
+		let drops = Cell::new(0);
+		let mut a = drop_counters(&drops, 20);
+		let mut passed = 0;
+		a.drain_each(|item| {
+			passed += 1;
+			assert_eq!(drops.get(), passed - 1);
+			drop(item);
+		});
+		assert_eq!(passed, 20);
+		assert_eq!(drops.get(), 20);
+		assert!(a.is_empty());
+		assert_eq!(a.extents(), 0);
+	}
+
+	#[test]
+	fn drop_drain_each_panic() {
+		use std::panic::{self, AssertUnwindSafe};
+		let drops = Cell::new(0);
+		let mut a = drop_counters(&drops, 5);
+		let mut passed = 0;
+		let result = panic::catch_unwind(AssertUnwindSafe(|| {
+			a.drain_each(|_item| {
+				passed += 1;
+				if passed == 2 {
+					panic!("callback panic");
+				}
+			})
+		}));
+		assert!(result.is_err());
+		assert_eq!(passed, 2);
+		assert_eq!(drops.get(), 5);
+		assert!(a.is_empty());
+	}
+
+	#[test]
+	fn i32_retain_unique() {
+		let mut a = ArrayList::from(&[1, 2, 1, 2, 3] as &[i32]);
+		a.retain_unique();
+		assert_eq!(a, [1, 2, 3]);
+	}
+
+	#[test]
+	fn i32_pairwise() {
+		let a = ArrayList::from(&[1, 3, 6, 10] as &[i32]);
+		let pairs: Vec<_> = a.pairwise().collect();
+		assert_eq!(pairs, [(&1, &3), (&3, &6), (&6, &10)]);
+		let single = ArrayList::from(&[1] as &[i32]);
+		assert_eq!(single.pairwise().count(), 0);
+	}
+
+	#[test]
+	fn i32_retain_range() {
+		let mut a: ArrayList<i32> = (0..10).collect();
+		a.retain_range(2..8, |x| x % 2 == 0);
+		assert_eq!(a, [0, 1, 2, 4, 6, 8, 9]);
+		a.retain_range(..=1, |&x| x != 0);
+		assert_eq!(a, [1, 2, 4, 6, 8, 9]);
+		a.retain_range(4.., |_| false);
+		assert_eq!(a, [1, 2, 4, 6]);
+	}
+
+	#[test]
+	#[should_panic(expected = "Index out of bounds")]
+	fn i32_retain_range_out_of_bounds() {
+		let mut a: ArrayList<i32> = (0..10).collect();
+		a.retain_range(5..11, |_| true);
+	}
+
+	#[test]
+	fn pair_eq_by() {
+		let a = ArrayList::from(&[(1u32, "one"), (2, "two"), (3, "three")][..]);
+		assert!(a.eq_by(&[1u32, 2, 3], |x, y| x.0 == *y));
+		assert!(!a.eq_by(&[1u32, 2, 4], |x, y| x.0 == *y));
+		assert!(!a.eq_by(&[1u32, 2], |x, y| x.0 == *y));
+	}
+
+	#[test]
+	fn i32_push_unchecked() {
+		let mut a = ArrayList::new();
+		a.reserve(100);
+		let extents = a.extents();
+		for i in 0..100 {
+			unsafe {
+				a.push_unchecked(i);
+			}
+		}
+		assert_eq!(a.extents(), extents);
+		assert_eq!(a.as_slice(), (0..100).collect::<Vec<i32>>().as_slice());
+	}
+
+	#[test]
+	fn i32_replace() {
+		let mut a = ArrayList::from(&[1, 2, 3] as &[i32]);
+		assert_eq!(a.replace(1, 5), 2);
+		assert_eq!(a, [1, 5, 3]);
+	}
+
+	#[test]
+	#[should_panic(expected = "Index out of bounds")]
+	fn i32_replace_out_of_bounds() {
+		let mut a = ArrayList::from(&[1, 2, 3] as &[i32]);
+		a.replace(3, 4);
+	}
+
+	#[test]
+	fn f64_sort_floats() {
+		let mut a = ArrayList::from(&[3.0, f64::NAN, 1.0, 2.0][..]);
+		a.sort_floats();
+		assert_eq!(&a.as_slice()[..3], [1.0, 2.0, 3.0]);
+		assert!(a[3].is_nan());
+		let mut b = ArrayList::from(&[0.5f32, -f32::NAN, -1.0][..]);
+		b.sort_floats();
+		assert!(b[0].is_nan());
+		assert_eq!(&b.as_slice()[1..], [-1.0, 0.5]);
+	}
+
+	#[test]
+	fn i32_try_with_capacity() {
+		assert_eq!(
+			ArrayList::<i32>::try_with_capacity(usize::MAX).err(),
+			Some(TryReserveError::CapacityOverflow)
+		);
+		let a = ArrayList::<i32>::try_with_capacity(20).unwrap();
+		assert_eq!(a.capacity(), 32);
+		assert!(a.is_empty());
+	}
+
+	#[test]
+	fn drop_into_drain_exhausted() {
+		let drops = Cell::new(0);
+		let a = drop_counters(&drops, 20);
+		let before = deallocations();
+		let mut drain = a.into_drain();
+		let mut seen = 0;
+		for item in &mut drain {
+			drop(item);
+			seen += 1;
+		}
+		assert_eq!(seen, 20);
+		assert_eq!(drops.get(), 20);
+		assert_eq!(deallocations(), before);
+		drop(drain);
+		assert_eq!(drops.get(), 20);
+		assert_eq!(deallocations() - before, 1);
+	}
+
+	#[test]
+	fn drop_into_drain_early() {
+		let drops = Cell::new(0);
+		let a = drop_counters(&drops, 20);
+		let before = deallocations();
+		let mut drain = a.into_drain();
+		drop(drain.next());
+		drop(drain.next());
+		assert_eq!(drops.get(), 2);
+		drop(drain);
+		assert_eq!(drops.get(), 20);
+		assert_eq!(deallocations() - before, 1);
+	}
+
+	#[test]
+	fn drop_clear_panic() {
+		use std::panic::{self, AssertUnwindSafe};
+		let drops = Cell::new(0);
+		let mut a = ArrayList::new();
+		for i in 0..5 {
+			a.push(PanicOnDrop(&drops, i == 2));
+		}
+		let result = panic::catch_unwind(AssertUnwindSafe(|| a.clear()));
+		assert!(result.is_err());
+		assert_eq!(drops.get(), 5);
+		assert!(a.is_empty());
+		drop(a);
+		assert_eq!(drops.get(), 5);
+	}
+
+	#[test]
+	fn usize_extend_loose_upper_bound() {
+		let a: ArrayList<usize> = (0..usize::MAX)
+			.take_while(|x| *x < 40)
+			.filter(|x| x % 2 == 0)
+			.collect();
+		assert_eq!(a.len(), 20);
+		assert_eq!(a.extents(), 2);
+		let mut b = ArrayList::from(&[7usize][..]);
+		b.extend((0..1usize << 44).take_while(|x| *x < 3));
+		assert_eq!(b, [7, 0, 1, 2]);
 	}
 }
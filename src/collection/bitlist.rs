@@ -0,0 +1,189 @@
+use crate::collection::arraylist::{ArrayList, IndexError};
+
+const WORD_BITS: usize = 64;
+
+/// A list of `bool`s packed 64 to a `u64` word.
+///
+/// Bit `i` is bit `i % 64` of word `i / 64`. Bits past `len` in the last
+/// word are always zero.
+pub struct BitList {
+	words: ArrayList<u64>,
+	len: usize,
+}
+
+impl BitList {
+	pub fn new() -> BitList {
+		BitList {
+			words: ArrayList::new(),
+			len: 0,
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	pub fn push(&mut self, bit: bool) {
+		if self.len.is_multiple_of(WORD_BITS) {
+			self.words.push(0);
+		}
+		self.len += 1;
+		self.set(self.len - 1, bit).unwrap();
+	}
+
+	pub fn pop(&mut self) -> Option<bool> {
+		if self.len == 0 {
+			return None;
+		}
+		let bit = self.get(self.len - 1);
+		self.set(self.len - 1, false).unwrap();
+		self.len -= 1;
+		if self.len.is_multiple_of(WORD_BITS) {
+			self.words.pop();
+		}
+		bit
+	}
+
+	pub fn get(&self, index: usize) -> Option<bool> {
+		if index >= self.len {
+			return None;
+		}
+		Some(self.words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0)
+	}
+
+	pub fn set(&mut self, index: usize, bit: bool) -> Result<(), IndexError> {
+		if index >= self.len {
+			return Err(IndexError {
+				index,
+				len: self.len,
+			});
+		}
+		let word = &mut self.words[index / WORD_BITS];
+		let mask = 1 << (index % WORD_BITS);
+		if bit {
+			*word |= mask;
+		} else {
+			*word &= !mask;
+		}
+		Ok(())
+	}
+
+	pub fn toggle(&mut self, index: usize) -> Result<(), IndexError> {
+		match self.get(index) {
+			Some(bit) => self.set(index, !bit),
+			None => Err(IndexError {
+				index,
+				len: self.len,
+			}),
+		}
+	}
+
+	pub fn count_ones(&self) -> usize {
+		self.words
+			.fold(0, |acc, word| acc + word.count_ones() as usize)
+	}
+
+	pub fn clear(&mut self) {
+		self.words.clear();
+		self.len = 0;
+	}
+
+	pub fn iter(&self) -> Iter<'_> {
+		Iter {
+			list: self,
+			index: 0,
+		}
+	}
+}
+
+impl Default for BitList {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+pub struct Iter<'a> {
+	list: &'a BitList,
+	index: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+	type Item = bool;
+
+	fn next(&mut self) -> Option<bool> {
+		let bit = self.list.get(self.index)?;
+		self.index += 1;
+		Some(bit)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remaining = self.list.len - self.index;
+		(remaining, Some(remaining))
+	}
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+impl<'a> IntoIterator for &'a BitList {
+	type Item = bool;
+	type IntoIter = Iter<'a>;
+
+	fn into_iter(self) -> Iter<'a> {
+		self.iter()
+	}
+}
+
+impl std::iter::FromIterator<bool> for BitList {
+	fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+		let mut list = BitList::new();
+		for bit in iter {
+			list.push(bit);
+		}
+		list
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn bitlist_push_toggle_count() {
+		let mut b = BitList::new();
+		for i in 0..200 {
+			b.push(i % 3 == 0);
+		}
+		assert_eq!(b.len(), 200);
+		assert_eq!(b.count_ones(), 67);
+		b.toggle(0).unwrap();
+		b.toggle(1).unwrap();
+		b.set(199, true).unwrap();
+		assert_eq!(b.count_ones(), 68);
+		assert_eq!(b.get(0), Some(false));
+		assert_eq!(b.get(1), Some(true));
+		assert_eq!(b.get(200), None);
+		assert_eq!(
+			b.set(200, true),
+			Err(IndexError {
+				index: 200,
+				len: 200
+			})
+		);
+		assert_eq!(b.iter().filter(|&bit| bit).count(), 68);
+	}
+
+	#[test]
+	fn bitlist_pop() {
+		let mut b: BitList = [true, false, true].iter().copied().collect();
+		assert_eq!(b.pop(), Some(true));
+		assert_eq!(b.pop(), Some(false));
+		assert_eq!(b.count_ones(), 1);
+		assert_eq!(b.pop(), Some(true));
+		assert_eq!(b.pop(), None);
+		assert!(b.is_empty());
+	}
+}
@@ -0,0 +1,266 @@
+use crate::collection::arraylist::ArrayList;
+use crate::collection::slotbuffer::SlotBuffer;
+use std::fmt::{self, Debug};
+
+/// A double-ended queue stored as a ring buffer.
+///
+/// Live elements occupy `len` slots starting at `head`, wrapping around the
+/// end of the buffer. The buffer shrinks as elements are popped, like a list
+/// with auto-shrink enabled.
+pub struct Deque<T> {
+	slots: SlotBuffer<T>,
+	head: usize,
+	len: usize,
+}
+
+impl<T> Deque<T> {
+	pub fn new() -> Deque<T> {
+		Self::with_capacity(0)
+	}
+
+	pub fn with_capacity(cap: usize) -> Deque<T> {
+		Deque {
+			slots: SlotBuffer::with_capacity(cap),
+			head: 0,
+			len: 0,
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	pub fn capacity(&self) -> usize {
+		self.slots.capacity()
+	}
+
+	pub fn push_back(&mut self, item: T) {
+		if self.len == self.capacity() {
+			self.grow();
+		}
+		let slot = self.slot(self.len);
+		self.slots.write(slot, item);
+		self.len += 1;
+	}
+
+	pub fn push_front(&mut self, item: T) {
+		if self.len == self.capacity() {
+			self.grow();
+		}
+		self.head = (self.head + self.capacity() - 1) % self.capacity();
+		self.slots.write(self.head, item);
+		self.len += 1;
+	}
+
+	pub fn pop_back(&mut self) -> Option<T> {
+		if self.len == 0 {
+			return None;
+		}
+		self.len -= 1;
+		let item = unsafe { self.slots.read(self.slot(self.len)) };
+		self.shrink();
+		Some(item)
+	}
+
+	pub fn pop_front(&mut self) -> Option<T> {
+		if self.len == 0 {
+			return None;
+		}
+		let item = unsafe { self.slots.read(self.head) };
+		self.head = (self.head + 1) % self.capacity();
+		self.len -= 1;
+		self.shrink();
+		Some(item)
+	}
+
+	pub fn get(&self, index: usize) -> Option<&T> {
+		if index >= self.len {
+			return None;
+		}
+		Some(unsafe { self.slots.get(self.slot(index)) })
+	}
+
+	pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+		if index >= self.len {
+			return None;
+		}
+		let slot = self.slot(index);
+		Some(unsafe { self.slots.get_mut(slot) })
+	}
+
+	pub fn front(&self) -> Option<&T> {
+		self.get(0)
+	}
+
+	pub fn back(&self) -> Option<&T> {
+		self.len.checked_sub(1).and_then(|index| self.get(index))
+	}
+
+	pub fn clear(&mut self) {
+		while self.pop_back().is_some() {}
+	}
+
+	pub fn iter(&self) -> Iter<'_, T> {
+		Iter {
+			deque: self,
+			front: 0,
+			back: self.len,
+		}
+	}
+
+	fn slot(&self, index: usize) -> usize {
+		(self.head + index) % self.capacity()
+	}
+
+	fn grow(&mut self) {
+		let old_cap = self.slots.grow();
+		// Move the segment running from head to the old end of the buffer up
+		// against the new end, so the elements that wrapped stay in order.
+		if self.head + self.len > old_cap {
+			let head_len = old_cap - self.head;
+			let new_head = self.capacity() - head_len;
+			self.slots.copy_within(self.head, new_head, head_len);
+			self.head = new_head;
+		}
+	}
+
+	fn shrink(&mut self) {
+		if ArrayList::<T>::capacity_for(self.len) >= self.capacity() {
+			return;
+		}
+		// Make the elements contiguous from slot 0 so the spare slots are all
+		// at the end and can be released.
+		self.slots.rotate_left(self.head);
+		self.head = 0;
+		self.slots.shrink_to(self.len);
+	}
+}
+
+impl<T> Default for Deque<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> Drop for Deque<T> {
+	fn drop(&mut self) {
+		let end = self.head + self.len;
+		let wrapped = end.saturating_sub(self.capacity());
+		unsafe {
+			self.slots.drop_range(self.head, end - wrapped);
+			self.slots.drop_range(0, wrapped);
+		}
+	}
+}
+
+impl<T: Debug> Debug for Deque<T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_list().entries(self.iter()).finish()
+	}
+}
+
+pub struct Iter<'a, T> {
+	deque: &'a Deque<T>,
+	front: usize,
+	back: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+	type Item = &'a T;
+
+	fn next(&mut self) -> Option<&'a T> {
+		if self.front == self.back {
+			return None;
+		}
+		self.front += 1;
+		self.deque.get(self.front - 1)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remaining = self.back - self.front;
+		(remaining, Some(remaining))
+	}
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+	fn next_back(&mut self) -> Option<&'a T> {
+		if self.front == self.back {
+			return None;
+		}
+		self.back -= 1;
+		self.deque.get(self.back)
+	}
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn i32_deque_mixed_ends() {
+		let mut d = Deque::new();
+		d.push_back(2);
+		d.push_front(1);
+		d.push_back(3);
+		d.push_front(0);
+		assert_eq!(d.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+		assert_eq!(d.pop_front(), Some(0));
+		assert_eq!(d.pop_back(), Some(3));
+		assert_eq!(d.front(), Some(&1));
+		assert_eq!(d.back(), Some(&2));
+		assert_eq!(d.pop_back(), Some(2));
+		assert_eq!(d.pop_back(), Some(1));
+		assert_eq!(d.pop_front(), None);
+		assert!(d.is_empty());
+	}
+
+	#[test]
+	fn i32_deque_wraparound_growth() {
+		let mut d = Deque::new();
+		for i in 0..10 {
+			d.push_back(i);
+		}
+		for i in 0..8 {
+			assert_eq!(d.pop_front(), Some(i));
+		}
+		// head is now at slot 8; these wrap past the end of the 16 slots
+		for i in 10..24 {
+			d.push_back(i);
+		}
+		assert_eq!(d.capacity(), 16);
+		// full and wrapped, so growing has to relocate the head segment
+		d.push_back(24);
+		d.push_front(7);
+		assert_eq!(d.capacity(), 32);
+		assert_eq!(
+			d.iter().copied().collect::<Vec<_>>(),
+			(7..25).collect::<Vec<_>>()
+		);
+		assert_eq!(d.iter().next_back(), Some(&24));
+	}
+
+	#[test]
+	fn string_deque_shrink_and_drop() {
+		let mut d = Deque::new();
+		for i in 0..40 {
+			d.push_front(i.to_string());
+		}
+		assert_eq!(d.capacity(), 48);
+		while d.len() > 3 {
+			d.pop_back();
+		}
+		assert_eq!(d.capacity(), 16);
+		assert_eq!(
+			d.iter().map(String::as_str).collect::<Vec<_>>(),
+			["39", "38", "37"]
+		);
+		*d.get_mut(1).unwrap() += "!";
+		assert_eq!(d.get(1).map(String::as_str), Some("38!"));
+	}
+}
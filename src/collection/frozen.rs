@@ -0,0 +1,90 @@
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// An immutable list produced by `ArrayList::freeze`.
+///
+/// Cloning shares the same buffer through an `Arc` rather than copying the
+/// elements.
+pub struct FrozenList<T> {
+	items: Arc<[T]>,
+}
+
+impl<T> FrozenList<T> {
+	pub(crate) fn new(items: Arc<[T]>) -> FrozenList<T> {
+		FrozenList { items }
+	}
+
+	pub fn len(&self) -> usize {
+		self.items.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	pub fn get(&self, index: usize) -> Option<&T> {
+		self.items.get(index)
+	}
+
+	pub fn iter(&self) -> std::slice::Iter<'_, T> {
+		self.items.iter()
+	}
+
+	pub fn as_slice(&self) -> &[T] {
+		&self.items
+	}
+}
+
+impl<T> Clone for FrozenList<T> {
+	fn clone(&self) -> Self {
+		FrozenList {
+			items: Arc::clone(&self.items),
+		}
+	}
+}
+
+impl<T> Deref for FrozenList<T> {
+	type Target = [T];
+
+	fn deref(&self) -> &[T] {
+		&self.items
+	}
+}
+
+impl<'a, T> IntoIterator for &'a FrozenList<T> {
+	type Item = &'a T;
+	type IntoIter = std::slice::Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<T: Debug> Debug for FrozenList<T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_list().entries(self.iter()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::collection::arraylist::ArrayList;
+
+	#[test]
+	fn string_freeze_shared_clones() {
+		let a: ArrayList<String> = ["x", "y", "z"].iter().map(|s| s.to_string()).collect();
+		let frozen = a.freeze();
+		let copy = frozen.clone();
+		assert!(std::ptr::eq(frozen.as_slice(), copy.as_slice()));
+		assert_eq!(frozen.len(), 3);
+		assert_eq!(copy.get(1).map(String::as_str), Some("y"));
+		assert_eq!(&frozen[1..], ["y", "z"]);
+		assert_eq!(
+			copy.iter().map(String::as_str).collect::<Vec<_>>(),
+			["x", "y", "z"]
+		);
+		drop(frozen);
+		assert_eq!(copy.get(2).map(String::as_str), Some("z"));
+	}
+}
@@ -0,0 +1,215 @@
+use crate::collection::arraylist::ArrayList;
+use crate::collection::slotbuffer::SlotBuffer;
+use std::fmt::{self, Debug};
+
+/// A sequence with a movable gap at the cursor, so edits near the cursor
+/// don't shift the rest of the elements.
+///
+/// Elements before the cursor occupy `0..gap_start` and those after it
+/// occupy `gap_end..capacity`. When the gap fills up, the elements after the
+/// cursor are moved to the new end of the grown buffer. The buffer is never
+/// shrunk.
+pub struct GapBuffer<T> {
+	slots: SlotBuffer<T>,
+	gap_start: usize,
+	gap_end: usize,
+}
+
+impl<T> GapBuffer<T> {
+	pub fn new() -> GapBuffer<T> {
+		GapBuffer {
+			slots: SlotBuffer::with_capacity(0),
+			gap_start: 0,
+			gap_end: 0,
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.capacity() - (self.gap_end - self.gap_start)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	pub fn capacity(&self) -> usize {
+		self.slots.capacity()
+	}
+
+	pub fn cursor(&self) -> usize {
+		self.gap_start
+	}
+
+	/// Moves the cursor so it sits just before the element at `index`.
+	///
+	/// Panics if `index > len()`.
+	pub fn move_cursor(&mut self, index: usize) {
+		if index > self.len() {
+			panic!("Index out of bounds");
+		}
+		if index < self.gap_start {
+			let count = self.gap_start - index;
+			self.slots.copy_within(index, self.gap_end - count, count);
+			self.gap_start -= count;
+			self.gap_end -= count;
+		} else {
+			let count = index - self.gap_start;
+			self.slots.copy_within(self.gap_end, self.gap_start, count);
+			self.gap_start += count;
+			self.gap_end += count;
+		}
+	}
+
+	/// Inserts `item` at the cursor and moves the cursor past it.
+	pub fn insert(&mut self, item: T) {
+		if self.gap_start == self.gap_end {
+			self.grow();
+		}
+		self.slots.write(self.gap_start, item);
+		self.gap_start += 1;
+	}
+
+	/// Removes and returns the element just after the cursor.
+	pub fn remove(&mut self) -> Option<T> {
+		if self.gap_end == self.capacity() {
+			return None;
+		}
+		let item = unsafe { self.slots.read(self.gap_end) };
+		self.gap_end += 1;
+		Some(item)
+	}
+
+	/// Removes and returns the element just before the cursor.
+	pub fn remove_before(&mut self) -> Option<T> {
+		if self.gap_start == 0 {
+			return None;
+		}
+		self.gap_start -= 1;
+		Some(unsafe { self.slots.read(self.gap_start) })
+	}
+
+	pub fn get(&self, index: usize) -> Option<&T> {
+		if index >= self.len() {
+			return None;
+		}
+		Some(unsafe { self.slots.get(self.slot(index)) })
+	}
+
+	pub fn to_arraylist(&self) -> ArrayList<T>
+	where
+		T: Clone,
+	{
+		let mut arraylist = ArrayList::with_capacity(self.len());
+		let (before, after) = self.segments();
+		arraylist.extend_from_slice(before);
+		arraylist.extend_from_slice(after);
+		arraylist
+	}
+
+	fn slot(&self, index: usize) -> usize {
+		if index < self.gap_start {
+			index
+		} else {
+			index + (self.gap_end - self.gap_start)
+		}
+	}
+
+	fn segments(&self) -> (&[T], &[T]) {
+		unsafe {
+			(
+				self.slots.slice(0, self.gap_start),
+				self.slots.slice(self.gap_end, self.capacity()),
+			)
+		}
+	}
+
+	fn grow(&mut self) {
+		let after = self.slots.grow() - self.gap_end;
+		let new_end = self.capacity() - after;
+		self.slots.copy_within(self.gap_end, new_end, after);
+		self.gap_end = new_end;
+	}
+}
+
+impl<T> Default for GapBuffer<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> Drop for GapBuffer<T> {
+	fn drop(&mut self) {
+		unsafe {
+			self.slots.drop_range(0, self.gap_start);
+			self.slots.drop_range(self.gap_end, self.capacity());
+		}
+	}
+}
+
+impl<T: Debug> Debug for GapBuffer<T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let (before, after) = self.segments();
+		fmt.debug_list().entries(before).entries(after).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn char_gap_buffer_editing() {
+		let mut g = GapBuffer::new();
+		for c in "held".chars() {
+			g.insert(c);
+		}
+		g.move_cursor(3);
+		g.insert('l');
+		g.insert('o');
+		g.insert(' ');
+		g.insert('w');
+		g.insert('o');
+		g.insert('r');
+		assert_eq!(g.cursor(), 9);
+		assert_eq!(
+			g.to_arraylist().into_iter().collect::<String>(),
+			"hello word"
+		);
+		g.move_cursor(9);
+		g.insert(' ');
+		assert_eq!(g.remove_before(), Some(' '));
+		g.insert('l');
+		g.move_cursor(0);
+		assert_eq!(g.remove(), Some('h'));
+		g.insert('H');
+		assert_eq!(g.get(4), Some(&'o'));
+		assert_eq!(g.len(), 11);
+		assert_eq!(
+			g.to_arraylist().into_iter().collect::<String>(),
+			"Hello world"
+		);
+	}
+
+	#[test]
+	fn string_gap_buffer_growth() {
+		let mut g = GapBuffer::new();
+		for i in 0..20 {
+			g.insert(i.to_string());
+		}
+		g.move_cursor(5);
+		for i in 100..120 {
+			g.insert(i.to_string());
+		}
+		assert_eq!(g.len(), 40);
+		assert_eq!(g.capacity(), 48);
+		let expected: Vec<String> = (0..5)
+			.chain(100..120)
+			.chain(5..20)
+			.map(|i| i.to_string())
+			.collect();
+		assert_eq!(g.to_arraylist().into_vec(), expected);
+		assert_eq!(g.remove().as_deref(), Some("5"));
+		g.move_cursor(g.len());
+		assert_eq!(g.remove(), None);
+	}
+}
@@ -0,0 +1,91 @@
+use crate::collection::arraylist::ArrayList;
+
+/// A binary min-heap over an `ArrayList`.
+///
+/// The children of the element at index `i` are at `2i + 1` and `2i + 2`.
+pub struct MinHeap<T: Ord> {
+	items: ArrayList<T>,
+}
+
+impl<T: Ord> MinHeap<T> {
+	pub fn new() -> MinHeap<T> {
+		MinHeap {
+			items: ArrayList::new(),
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.items.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	pub fn peek(&self) -> Option<&T> {
+		self.items.first()
+	}
+
+	pub fn push(&mut self, item: T) {
+		self.items.push(item);
+		let s = self.items.as_mut_slice();
+		let mut i = s.len() - 1;
+		while i > 0 {
+			let parent = (i - 1) / 2;
+			if s[i] >= s[parent] {
+				break;
+			}
+			s.swap(i, parent);
+			i = parent;
+		}
+	}
+
+	pub fn pop(&mut self) -> Option<T> {
+		let item = self.items.swap_remove_front()?;
+		let s = self.items.as_mut_slice();
+		let mut i = 0;
+		loop {
+			let left = 2 * i + 1;
+			let right = left + 1;
+			let mut smallest = i;
+			if left < s.len() && s[left] < s[smallest] {
+				smallest = left;
+			}
+			if right < s.len() && s[right] < s[smallest] {
+				smallest = right;
+			}
+			if smallest == i {
+				break;
+			}
+			s.swap(i, smallest);
+			i = smallest;
+		}
+		Some(item)
+	}
+}
+
+impl<T: Ord> Default for MinHeap<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn i32_min_heap_order() {
+		let mut h = MinHeap::new();
+		for &x in &[5, 1, 3, 2, 4] {
+			h.push(x);
+		}
+		assert_eq!(h.peek(), Some(&1));
+		let mut out = Vec::new();
+		while let Some(x) = h.pop() {
+			out.push(x);
+		}
+		assert_eq!(out, [1, 2, 3, 4, 5]);
+		assert!(h.is_empty());
+	}
+}
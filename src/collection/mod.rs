@@ -1 +1,13 @@
+pub mod arena;
 pub mod arraylist;
+pub mod bitlist;
+pub mod deque;
+pub mod frozen;
+pub mod gapbuffer;
+pub mod heap;
+mod slotbuffer;
+pub mod smallarraylist;
+pub mod sortedlist;
+pub mod stack;
+#[cfg(test)]
+mod test_alloc;
@@ -0,0 +1,108 @@
+use crate::collection::arraylist::ArrayList;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::slice;
+
+/// Raw, possibly uninitialised slots allocated in extents.
+///
+/// This is an `ArrayList<MaybeUninit<T>>` whose length is kept equal to its
+/// capacity, so every slot is addressable and the buffer grows and shrinks
+/// one extent at a time like a plain list. The owner tracks which slots hold
+/// live values and must drop them itself.
+pub(crate) struct SlotBuffer<T> {
+	slots: ArrayList<MaybeUninit<T>>,
+}
+
+impl<T> SlotBuffer<T> {
+	pub(crate) fn with_capacity(cap: usize) -> SlotBuffer<T> {
+		let mut slots = ArrayList::with_capacity(cap);
+		unsafe {
+			slots.set_len(slots.capacity());
+		}
+		SlotBuffer { slots }
+	}
+
+	pub(crate) fn capacity(&self) -> usize {
+		self.slots.len()
+	}
+
+	/// Adds one extent of slots at the end and returns the old capacity.
+	pub(crate) fn grow(&mut self) -> usize {
+		let old_cap = self.capacity();
+		self.slots.reserve_extents(self.slots.extents() + 1);
+		unsafe {
+			self.slots.set_len(self.slots.capacity());
+		}
+		old_cap
+	}
+
+	/// Releases the extents not needed to hold slots `0..len`.
+	pub(crate) fn shrink_to(&mut self, len: usize) {
+		unsafe {
+			self.slots.set_len(len);
+		}
+		self.slots.shrink_to_fit();
+		unsafe {
+			self.slots.set_len(self.slots.capacity());
+		}
+	}
+
+	pub(crate) fn rotate_left(&mut self, mid: usize) {
+		self.slots.as_mut_slice().rotate_left(mid);
+	}
+
+	pub(crate) fn write(&mut self, index: usize, item: T) {
+		self.slots[index] = MaybeUninit::new(item);
+	}
+
+	/// # Safety
+	///
+	/// The slot must hold a live value, which is moved out.
+	pub(crate) unsafe fn read(&self, index: usize) -> T {
+		self.slots[index].as_ptr().read()
+	}
+
+	/// # Safety
+	///
+	/// The slot must hold a live value.
+	pub(crate) unsafe fn get(&self, index: usize) -> &T {
+		&*self.slots[index].as_ptr()
+	}
+
+	/// # Safety
+	///
+	/// The slot must hold a live value.
+	pub(crate) unsafe fn get_mut(&mut self, index: usize) -> &mut T {
+		&mut *self.slots[index].as_mut_ptr()
+	}
+
+	/// # Safety
+	///
+	/// Every slot in `start..end` must hold a live value.
+	pub(crate) unsafe fn slice(&self, start: usize, end: usize) -> &[T] {
+		let slots = &self.slots.as_slice()[start..end];
+		slice::from_raw_parts(slots.as_ptr() as *const T, slots.len())
+	}
+
+	/// Moves `count` slots from `src` to `dst`; the ranges may overlap.
+	pub(crate) fn copy_within(&mut self, src: usize, dst: usize, count: usize) {
+		let slots = self.slots.as_mut_slice();
+		assert!(src + count <= slots.len() && dst + count <= slots.len());
+		let buf = slots.as_mut_ptr();
+		unsafe {
+			ptr::copy(buf.add(src), buf.add(dst), count);
+		}
+	}
+
+	/// # Safety
+	///
+	/// Every slot in `start..end` must hold a live value, and is left
+	/// uninitialised.
+	pub(crate) unsafe fn drop_range(&mut self, start: usize, end: usize) {
+		let slots = &mut self.slots.as_mut_slice()[start..end];
+		ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+			slots.as_mut_ptr() as *mut T,
+			slots.len(),
+		));
+	}
+}
@@ -0,0 +1,163 @@
+use crate::collection::arraylist::ArrayList;
+use std::fmt::{self, Debug};
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+use std::slice;
+
+enum Storage<T, const N: usize> {
+	Inline {
+		buf: [MaybeUninit<T>; N],
+		len: usize,
+	},
+	Heap(ArrayList<T>),
+}
+
+/// A list holding up to `N` elements inline, spilling to an `ArrayList` on
+/// the heap once it grows past that.
+///
+/// Once spilled it stays on the heap, even if elements are popped.
+pub struct SmallArrayList<T, const N: usize> {
+	storage: Storage<T, N>,
+}
+
+impl<T, const N: usize> SmallArrayList<T, N> {
+	pub fn new() -> SmallArrayList<T, N> {
+		SmallArrayList {
+			storage: Storage::Inline {
+				buf: [const { MaybeUninit::uninit() }; N],
+				len: 0,
+			},
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		match &self.storage {
+			Storage::Inline { len, .. } => *len,
+			Storage::Heap(list) => list.len(),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	pub fn spilled(&self) -> bool {
+		matches!(self.storage, Storage::Heap(_))
+	}
+
+	pub fn push(&mut self, item: T) {
+		match &mut self.storage {
+			Storage::Inline { buf, len } if *len < N => {
+				buf[*len] = MaybeUninit::new(item);
+				*len += 1;
+			}
+			Storage::Inline { buf, len } => {
+				let mut list = ArrayList::with_capacity(N + 1);
+				// The inline length is zeroed first so the moved-out elements
+				// are not dropped again if the push below panics.
+				let count = mem::replace(len, 0);
+				for slot in &buf[..count] {
+					list.push(unsafe { slot.as_ptr().read() });
+				}
+				list.push(item);
+				self.storage = Storage::Heap(list);
+			}
+			Storage::Heap(list) => list.push(item),
+		}
+	}
+
+	pub fn pop(&mut self) -> Option<T> {
+		match &mut self.storage {
+			Storage::Inline { len: 0, .. } => None,
+			Storage::Inline { buf, len } => {
+				*len -= 1;
+				Some(unsafe { buf[*len].as_ptr().read() })
+			}
+			Storage::Heap(list) => list.pop(),
+		}
+	}
+
+	pub fn get(&self, index: usize) -> Option<&T> {
+		self.as_slice().get(index)
+	}
+
+	pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+		self.as_mut_slice().get_mut(index)
+	}
+
+	pub fn as_slice(&self) -> &[T] {
+		match &self.storage {
+			Storage::Inline { buf, len } => unsafe {
+				slice::from_raw_parts(buf.as_ptr() as *const T, *len)
+			},
+			Storage::Heap(list) => list.as_slice(),
+		}
+	}
+
+	pub fn as_mut_slice(&mut self) -> &mut [T] {
+		match &mut self.storage {
+			Storage::Inline { buf, len } => unsafe {
+				slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut T, *len)
+			},
+			Storage::Heap(list) => list.as_mut_slice(),
+		}
+	}
+}
+
+impl<T, const N: usize> Default for SmallArrayList<T, N> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T, const N: usize> Drop for SmallArrayList<T, N> {
+	fn drop(&mut self) {
+		if let Storage::Inline { buf, len } = &mut self.storage {
+			unsafe {
+				ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+					buf.as_mut_ptr() as *mut T,
+					*len,
+				));
+			}
+		}
+	}
+}
+
+impl<T: Debug, const N: usize> Debug for SmallArrayList<T, N> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_list().entries(self.as_slice()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::collection::test_alloc::allocations;
+
+	#[test]
+	fn i32_small_stays_inline() {
+		let before = allocations();
+		let mut a: SmallArrayList<i32, 4> = SmallArrayList::new();
+		a.push(1);
+		a.push(2);
+		a.push(3);
+		assert!(!a.spilled());
+		assert_eq!(a.len(), 3);
+		assert_eq!(a.get(2), Some(&3));
+		assert_eq!(a.get(3), None);
+		drop(a);
+		assert_eq!(allocations(), before);
+	}
+
+	#[test]
+	fn string_small_spills() {
+		let mut a: SmallArrayList<String, 4> = SmallArrayList::new();
+		for i in 0..5 {
+			a.push(i.to_string());
+		}
+		assert!(a.spilled());
+		assert_eq!(a.as_slice(), ["0", "1", "2", "3", "4"]);
+		assert_eq!(a.pop().as_deref(), Some("4"));
+		assert_eq!(a.len(), 4);
+	}
+}
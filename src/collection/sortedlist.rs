@@ -0,0 +1,118 @@
+use crate::collection::arraylist::ArrayList;
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+
+/// An `ArrayList` kept in ascending order, answering order-statistics
+/// queries by binary search.
+pub struct SortedList<T: Ord> {
+	items: ArrayList<T>,
+}
+
+impl<T: Ord> SortedList<T> {
+	pub fn new() -> SortedList<T> {
+		SortedList {
+			items: ArrayList::new(),
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.items.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	pub fn insert(&mut self, item: T) -> usize {
+		self.items.insert_sorted(item)
+	}
+
+	pub fn remove(&mut self, value: &T) -> Option<T> {
+		let index = self.rank(value);
+		if self.items.as_slice().get(index) == Some(value) {
+			Some(self.items.remove(index))
+		} else {
+			None
+		}
+	}
+
+	pub fn contains(&self, value: &T) -> bool {
+		self.items.binary_search(value).is_ok()
+	}
+
+	/// Returns the number of elements equal to `value`.
+	pub fn count(&self, value: &T) -> usize {
+		self.upper_bound(value) - self.rank(value)
+	}
+
+	/// Returns the number of elements less than `value`.
+	pub fn rank(&self, value: &T) -> usize {
+		self.items.as_slice().partition_point(|x| x < value)
+	}
+
+	/// Returns the elements in `low..high`.
+	pub fn range(&self, low: &T, high: &T) -> &[T] {
+		let start = self.rank(low);
+		let end = self.rank(high).max(start);
+		&self.items.as_slice()[start..end]
+	}
+
+	pub fn into_inner(self) -> ArrayList<T> {
+		self.items
+	}
+
+	fn upper_bound(&self, value: &T) -> usize {
+		self.items.as_slice().partition_point(|x| x <= value)
+	}
+}
+
+impl<T: Ord> Default for SortedList<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Ord> Deref for SortedList<T> {
+	type Target = [T];
+
+	fn deref(&self) -> &[T] {
+		self.items.as_slice()
+	}
+}
+
+impl<T: Ord> std::iter::FromIterator<T> for SortedList<T> {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		let mut items: ArrayList<T> = iter.into_iter().collect();
+		items.sort();
+		SortedList { items }
+	}
+}
+
+impl<T: Ord + Debug> Debug for SortedList<T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_list().entries(self.iter()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn i32_sorted_list_order_statistics() {
+		let mut s = SortedList::new();
+		for &x in &[3, 2, 1, 2] {
+			s.insert(x);
+		}
+		assert_eq!(&s[..], [1, 2, 2, 3]);
+		assert_eq!(s.count(&2), 2);
+		assert_eq!(s.count(&4), 0);
+		assert_eq!(s.rank(&3), 3);
+		assert_eq!(s.rank(&0), 0);
+		assert_eq!(s.range(&2, &3), [2, 2]);
+		assert_eq!(s.range(&3, &1), [] as [i32; 0]);
+		assert_eq!(s.remove(&2), Some(2));
+		assert_eq!(s.remove(&5), None);
+		assert_eq!(s.count(&2), 1);
+	}
+}
@@ -0,0 +1,83 @@
+use crate::collection::arraylist::ArrayList;
+use std::ops::Deref;
+
+/// A LIFO stack over an `ArrayList`.
+///
+/// Only the top of the stack can be changed. It derefs to a read-only slice,
+/// bottom first, for inspection.
+pub struct Stack<T>(ArrayList<T>);
+
+impl<T> Stack<T> {
+	pub fn new() -> Stack<T> {
+		Stack(ArrayList::new())
+	}
+
+	pub fn with_capacity(cap: usize) -> Stack<T> {
+		Stack(ArrayList::with_capacity(cap))
+	}
+
+	pub fn push(&mut self, item: T) {
+		self.0.push(item);
+	}
+
+	pub fn pop(&mut self) -> Option<T> {
+		self.0.pop()
+	}
+
+	pub fn peek(&self) -> Option<&T> {
+		self.0.last()
+	}
+
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	pub fn into_inner(self) -> ArrayList<T> {
+		self.0
+	}
+}
+
+impl<T> Default for Stack<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> From<ArrayList<T>> for Stack<T> {
+	fn from(list: ArrayList<T>) -> Self {
+		Stack(list)
+	}
+}
+
+impl<T> Deref for Stack<T> {
+	type Target = [T];
+
+	fn deref(&self) -> &[T] {
+		self.0.as_slice()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn i32_stack_lifo() {
+		let mut s = Stack::new();
+		s.push(1);
+		s.push(2);
+		s.push(3);
+		assert_eq!(s.len(), 3);
+		assert_eq!(s.peek(), Some(&3));
+		assert_eq!(&s[..], [1, 2, 3]);
+		assert_eq!(s.pop(), Some(3));
+		assert_eq!(s.pop(), Some(2));
+		assert_eq!(s.pop(), Some(1));
+		assert_eq!(s.pop(), None);
+		assert!(s.is_empty());
+	}
+}
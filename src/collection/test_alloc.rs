@@ -0,0 +1,46 @@
+//! Global allocator for the test binary that counts calls per thread, so
+//! tests can assert how often a collection hits the allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAlloc;
+
+thread_local! {
+	static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+	static DEALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+	let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+/// Number of `alloc` and `realloc` calls made so far on this thread.
+pub(crate) fn allocations() -> usize {
+	ALLOCATIONS.with(|count| count.get())
+}
+
+/// Number of `dealloc` calls made so far on this thread.
+pub(crate) fn deallocations() -> usize {
+	DEALLOCATIONS.with(|count| count.get())
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		count_allocation();
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		let _ = DEALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+		System.dealloc(ptr, layout)
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		count_allocation();
+		System.realloc(ptr, layout, new_size)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;